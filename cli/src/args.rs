@@ -0,0 +1,146 @@
+use std::{error::Error, path::PathBuf};
+
+use transformer::{Options, Syntax};
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Flags {
+    pub re: Option<String>,
+    pub target: Option<String>,
+    pub suffix: Option<String>,
+    pub source_map: Option<String>,
+    pub named_views: Option<String>,
+    pub runtime: Option<String>,
+    pub import_style: Option<String>,
+    pub mode: Option<String>,
+    pub verbose: bool,
+    pub watch: bool,
+    pub check: bool,
+    pub show_help: bool,
+    pub show_version: bool,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Params {
+    pub input_path: Option<PathBuf>,
+    pub output_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Args {
+    pub flags: Flags,
+    pub params: Params,
+}
+
+/// Parses the arguments the same way as `parseArgs` of the node CLI does.
+pub fn parse_args<S: AsRef<str>>(args: &[S]) -> Result<Args, String> {
+    let mut flags = Flags::default();
+    let mut i = 0;
+    while let Some(arg) = args.get(i).map(|s| s.as_ref()) {
+        if !arg.starts_with('-') {
+            break;
+        }
+        let value = match arg {
+            "--re" => &mut flags.re,
+            "--target" => &mut flags.target,
+            "--suffix" => &mut flags.suffix,
+            "--source-map" => &mut flags.source_map,
+            "--named-views" => &mut flags.named_views,
+            "--runtime" => &mut flags.runtime,
+            "--import-style" => &mut flags.import_style,
+            "--mode" => &mut flags.mode,
+            _ => {
+                let flag = match arg {
+                    "--verbose" => &mut flags.verbose,
+                    "--watch" => &mut flags.watch,
+                    "--check" => &mut flags.check,
+                    "--help" => &mut flags.show_help,
+                    "--version" => &mut flags.show_version,
+                    _ => return Err(format!("Unknown option: '{arg}'")),
+                };
+                *flag = true;
+                i += 1;
+                continue;
+            }
+        };
+        let Some(v) = args.get(i + 1) else {
+            return Err(format!("No value for option: '{arg}'"));
+        };
+        *value = Some(v.as_ref().to_string());
+        i += 2;
+    }
+    Ok(Args {
+        flags,
+        params: Params {
+            input_path: args.get(i).map(|s| PathBuf::from(s.as_ref())),
+            output_dir: args.get(i + 1).map(|s| PathBuf::from(s.as_ref())),
+        },
+    })
+}
+
+impl Flags {
+    /// Transformer options for a file of the syntax
+    pub fn options(&self, syntax: Syntax) -> Result<Options, Box<dyn Error>> {
+        let mut options = Options::try_new(syntax, self.target.as_deref(), Some(self.verbose))?;
+        options.source_map = self.source_map.as_deref().map(str::parse).transpose()?;
+        if let Some(named_views) = self.named_views.as_deref() {
+            options.named_views = named_views.parse()?;
+        }
+        if let Some(runtime) = &self.runtime {
+            options.runtime = runtime.clone();
+        }
+        if let Some(import_style) = self.import_style.as_deref() {
+            options.import_style = import_style.parse()?;
+        }
+        if let Some(mode) = self.mode.as_deref() {
+            options.mode = mode.parse()?;
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args::<&str>(&[]).unwrap(), Args::default());
+        let args =
+            parse_args(&["--re", ".*", "--suffix", "-view", "--watch", "in", "out"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                flags: Flags {
+                    re: Some(".*".to_string()),
+                    suffix: Some("-view".to_string()),
+                    watch: true,
+                    ..Default::default()
+                },
+                params: Params {
+                    input_path: Some("in".into()),
+                    output_dir: Some("out".into()),
+                },
+            }
+        );
+        assert_eq!(
+            parse_args(&["-unknown"]).unwrap_err(),
+            "Unknown option: '-unknown'"
+        );
+        assert!(parse_args(&["--target"]).is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        let flags = Flags {
+            mode: Some("ssr".to_string()),
+            ..Default::default()
+        };
+        assert!(flags.options(Syntax::Js).is_ok());
+        let flags = Flags {
+            mode: Some("lorem ipsum".to_string()),
+            ..Default::default()
+        };
+        assert!(flags.options(Syntax::Js).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use std::{env, fs, path::Path, process::ExitCode};
+
+use regex::Regex;
+use transformer::{ES_DEFAULT_VERSION, ES_SUPPORTED_VERSIONS, VERSION};
+
+use self::{
+    args::{parse_args, Flags},
+    run::{collect_files, Job},
+    watch::watch,
+};
+
+mod args;
+mod run;
+mod watch;
+
+const DEFAULT_RE: &str = r"\.(jsx|tsx)$";
+
+const WATCHING_MSG: &str = "\nWatching for changes...\n";
+
+fn usage_text() -> String {
+    let versions = ES_SUPPORTED_VERSIONS
+        .iter()
+        .map(|s| {
+            if s == &ES_DEFAULT_VERSION {
+                format!("{s} (by default)")
+            } else {
+                s.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!(
+        r#"Usage: viewmill [OPTIONS] INPUT_PATH [OUTPUT_DIR]
+
+Arguments:
+  INPUT_PATH       File or directory. By default searches for all *.{{jsx,tsx}} files in a directory. Use the `--re` option to set your own filter.
+  [OUTPUT_DIR]     Output directory
+
+Options:
+  --re             A regular expression to filter files in a directory (see the examples)
+  --target         Output js target: {versions}
+  --suffix         How to suffix output file names
+  --source-map     Generates source maps: "inline" or "separate" (as *.map files)
+  --named-views    Which named exports are views besides the default one: "none" (by default), "capitalized" or "marked" (by the `/* @view */` comment)
+  --runtime        The runtime module specifier, "viewmill-runtime" by default
+  --import-style   How to import the runtime: "namespace" (by default), "named" (only the used helpers) or "require" (CommonJS)
+  --mode           What views are transformed into: "dom" (by default), "ssr" (rendering HTML strings), "hydrate" (adopting server-rendered DOM) or "element" (defining custom elements)
+  --verbose        Shows warnings and files as they are transformed
+  --watch          Starts watching for changes
+  --check          Verifies the existing outputs are up to date without writing anything
+  --help           Prints this message
+  --version        Prints the current version
+
+Examples:
+  1. Transform file:
+        viewmill path/to/file.jsx
+  2. Only *.jsx files:
+        viewmill --re "\.(jsx)$" path/to/dir
+  3. Set target:
+        viewmill --target esnext path/to/smth
+  4. Suffix to get "*-view.js" as an output for "*.jsx":
+        viewmill --suffix "-view" path/to/dir
+  5. Fail if the generated files are outdated, e.g. on CI:
+        viewmill --check --suffix "-view" path/to/dir"#
+    )
+}
+
+fn print_usage_error(msg: &str) {
+    eprintln!("[error] {msg}");
+    println!("\n{}", usage_text());
+}
+
+fn transform(input_path: &Path, output_dir: Option<&Path>, flags: &Flags) -> Result<bool, String> {
+    let stats = fs::metadata(input_path).map_err(|e| format!("{}: {e}", input_path.display()))?;
+    if stats.is_dir() {
+        let re =
+            Regex::new(flags.re.as_deref().unwrap_or(DEFAULT_RE)).map_err(|e| e.to_string())?;
+        let job = Job {
+            input_dir: Some(input_path),
+            output_dir,
+            flags,
+        };
+        let list = || collect_files(input_path, &re);
+        let ok = job.transform_files(&list().map_err(|e| e.to_string())?);
+        if flags.watch {
+            println!("{WATCHING_MSG}");
+            watch(
+                list,
+                |files| _ = job.transform_files(files),
+                |files| job.report_orphaned(files),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(ok)
+    } else {
+        let job = Job {
+            input_dir: None,
+            output_dir,
+            flags,
+        };
+        let result = job.transform_file(input_path);
+        if flags.watch {
+            if let Err(e) = &result {
+                eprintln!("{e}");
+            }
+            println!("{WATCHING_MSG}");
+            let list = || Ok(vec![input_path.to_path_buf()]);
+            watch(
+                list,
+                |files| _ = job.transform_files(files),
+                |files| job.report_orphaned(files),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        result.map(|_| true)
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() {
+        println!("{}", usage_text());
+        return ExitCode::FAILURE;
+    }
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            print_usage_error(&e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let flags = args.flags;
+    if flags.show_help {
+        println!("{}", usage_text());
+        ExitCode::SUCCESS
+    } else if flags.show_version {
+        println!("{VERSION}");
+        ExitCode::SUCCESS
+    } else if let Some(input_path) = args.params.input_path {
+        match transform(&input_path, args.params.output_dir.as_deref(), &flags) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }
+    } else {
+        print_usage_error("No path provided");
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,272 @@
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Instant,
+};
+
+use regex::Regex;
+use transformer::{check_named_str, tr_named_str, Diagnostic, Diagnostics, SourceMapKind, Syntax};
+
+use crate::args::Flags;
+
+/// Where and how files are transformed
+pub struct Job<'a> {
+    pub input_dir: Option<&'a Path>,
+    pub output_dir: Option<&'a Path>,
+    pub flags: &'a Flags,
+}
+
+impl Job<'_> {
+    /// The output path without an extension
+    fn output_base(&self, input_path: &Path) -> PathBuf {
+        let parent = input_path.parent().unwrap_or(Path::new(""));
+        let dir = match (self.input_dir, self.output_dir) {
+            (Some(input_dir), Some(output_dir)) => {
+                output_dir.join(parent.strip_prefix(input_dir).unwrap_or(Path::new("")))
+            }
+            (None, Some(output_dir)) => output_dir.to_path_buf(),
+            (_, None) => parent.to_path_buf(),
+        };
+        let mut name = input_path.file_stem().unwrap_or_default().to_os_string();
+        if let Some(suffix) = &self.flags.suffix {
+            name.push(suffix);
+        }
+        dir.join(name)
+    }
+
+    pub fn transform_file(&self, input_path: &Path) -> Result<(), String> {
+        let err = |e: io::Error| format!("{}: {e}", input_path.display());
+        let syntax = Syntax::from_path(input_path)
+            .ok_or_else(|| format!("Unknown file type at \"{}\"", input_path.display()))?;
+        let options = self.flags.options(syntax).map_err(|e| e.to_string())?;
+        let output_base = self.output_base(input_path);
+        let output_dir = output_base.parent().unwrap_or(Path::new(""));
+        let output_path = with_ext(&output_base, syntax.ext());
+        let map_path = (options.source_map == Some(SourceMapKind::Separate))
+            .then(|| with_ext(&output_path, "map"));
+        // The separate source map is referred by the output
+        let map_url = map_path.as_ref().map(|p| {
+            format!(
+                "//# sourceMappingURL={}\n",
+                p.file_name().unwrap_or_default().to_string_lossy()
+            )
+        });
+        let time_start = Instant::now();
+        let input = fs::read_to_string(input_path).map_err(err)?;
+        // Source maps refer to the input relatively to the output
+        let name = relative_path(output_dir, input_path).map_err(err)?;
+        let name = name.to_string_lossy();
+        let diagnostics_err = |e: Diagnostics| {
+            e.0.iter()
+                .map(|d| format_diagnostic(input_path, d))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        if self.flags.check {
+            let existing = fs::read_to_string(&output_path)
+                .map_err(|e| format!("{}: {e}", output_path.display()))?;
+            let existing = match &map_url {
+                Some(url) => existing
+                    .strip_suffix(url.as_str())
+                    .ok_or_else(|| format!("{}: no source map reference", output_path.display()))?,
+                None => &existing,
+            };
+            let check =
+                check_named_str(&name, &input, options, existing).map_err(diagnostics_err)?;
+            self.print_diagnostics(input_path, &check.output.diagnostics);
+            if let Some(diff) = check.diff {
+                return Err(format!("{} is out of date:\n{diff}", output_path.display()));
+            }
+            if let (Some(map_path), Some(map)) = (&map_path, &check.output.map) {
+                let existing = fs::read_to_string(map_path)
+                    .map_err(|e| format!("{}: {e}", map_path.display()))?;
+                if &existing != map {
+                    return Err(format!("{} is out of date", map_path.display()));
+                }
+            }
+            if self.flags.verbose {
+                println!("\"{}\" is up to date", output_path.display());
+            }
+            return Ok(());
+        }
+        let output = tr_named_str(&name, &input, options).map_err(diagnostics_err)?;
+        self.print_diagnostics(input_path, &output.diagnostics);
+        if self.flags.verbose {
+            println!(
+                "Transformed \"{}\" in {}ms",
+                input_path.display(),
+                time_start.elapsed().as_millis()
+            );
+        }
+        fs::create_dir_all(output_dir).map_err(err)?;
+        let src = match (output.map, map_path, map_url) {
+            (Some(map), Some(map_path), Some(map_url)) => {
+                self.write(input_path, &map_path, &map).map_err(err)?;
+                output.src + map_url.as_str()
+            }
+            _ => output.src,
+        };
+        self.write(input_path, &output_path, &src).map_err(err)
+    }
+
+    fn print_diagnostics(&self, input_path: &Path, diagnostics: &[Diagnostic]) {
+        if self.flags.verbose {
+            for d in diagnostics.iter() {
+                eprintln!("{}", format_diagnostic(input_path, d));
+            }
+        }
+    }
+
+    fn write(&self, input_path: &Path, output_path: &Path, s: &str) -> io::Result<()> {
+        if self.flags.verbose {
+            println!(" * {} -> {}", input_path.display(), output_path.display());
+        }
+        fs::write(output_path, s)
+    }
+
+    /// Reports the outputs left by the deleted files, which are kept to be removed manually
+    pub fn report_orphaned(&self, files: &[PathBuf]) {
+        for input_path in files {
+            let Some(syntax) = Syntax::from_path(input_path) else {
+                continue;
+            };
+            let output_path = with_ext(&self.output_base(input_path), syntax.ext());
+            let map_path = with_ext(&output_path, "map");
+            for path in [output_path, map_path] {
+                if path.exists() {
+                    eprintln!(
+                        "Orphaned \"{}\" as \"{}\" is deleted",
+                        path.display(),
+                        input_path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    /// Transforms the files in parallel printing errors. Returns `false` if any has failed.
+    pub fn transform_files(&self, files: &[PathBuf]) -> bool {
+        let next = AtomicUsize::new(0);
+        let failed = AtomicUsize::new(0);
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(files.len());
+        thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| {
+                    while let Some(path) = files.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if let Err(e) = self.transform_file(path) {
+                            eprintln!("{e}");
+                            failed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+        failed.into_inner() == 0
+    }
+}
+
+/// Recursively collects all the files matching the regular expression
+pub fn collect_files(dir: &Path, re: &Regex) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let ty = fs::metadata(&path)?.file_type();
+        if ty.is_dir() {
+            files.append(&mut collect_files(&path, re)?);
+        } else if ty.is_file() && re.is_match(&path.to_string_lossy()) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn with_ext(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(".");
+    path.push(ext);
+    PathBuf::from(path)
+}
+
+fn format_diagnostic(input_path: &Path, d: &Diagnostic) -> String {
+    let file = Diagnostic {
+        file: Some(input_path.display().to_string()),
+        ..d.clone()
+    };
+    file.to_string()
+}
+
+fn relative_path(from_dir: &Path, to: &Path) -> io::Result<PathBuf> {
+    Ok(relative_components(
+        &std::path::absolute(from_dir)?,
+        &std::path::absolute(to)?,
+    ))
+}
+
+fn relative_components(from_dir: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    for c in &to[common..] {
+        path.push(c);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_output_base() {
+        let flags = Flags {
+            suffix: Some("-view".to_string()),
+            ..Default::default()
+        };
+        let job = Job {
+            input_dir: Some(Path::new("src")),
+            output_dir: Some(Path::new("out")),
+            flags: &flags,
+        };
+        assert_eq!(
+            job.output_base(Path::new("src/a/b.tsx")),
+            PathBuf::from("out/a/b-view")
+        );
+        let job = Job {
+            input_dir: None,
+            output_dir: None,
+            flags: &flags,
+        };
+        assert_eq!(
+            job.output_base(Path::new("src/a/b.tsx")),
+            PathBuf::from("src/a/b-view")
+        );
+    }
+
+    #[test]
+    fn test_relative_components() {
+        assert_eq!(
+            relative_components(Path::new("/a/out/b"), Path::new("/a/src/b/c.jsx")),
+            PathBuf::from("../../src/b/c.jsx")
+        );
+        assert_eq!(
+            relative_components(Path::new("/a/src"), Path::new("/a/src/c.jsx")),
+            PathBuf::from("c.jsx")
+        );
+    }
+}
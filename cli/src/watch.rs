@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Polls the listed files forever calling `on_change` with the ones modified or added
+/// since the previous check, and `on_delete` with the ones gone since then.
+pub fn watch(
+    mut list: impl FnMut() -> io::Result<Vec<PathBuf>>,
+    mut on_change: impl FnMut(&[PathBuf]),
+    mut on_delete: impl FnMut(&[PathBuf]),
+) -> io::Result<()> {
+    let mut times = modified_times(list()?);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let next = modified_times(list()?);
+        let changed: Vec<PathBuf> = next
+            .iter()
+            .filter(|(path, time)| times.get(*path) != Some(*time))
+            .map(|(path, _)| path.clone())
+            .collect();
+        let deleted: Vec<PathBuf> = times
+            .into_keys()
+            .filter(|path| !next.contains_key(path))
+            .collect();
+        times = next;
+        if !changed.is_empty() {
+            on_change(&changed);
+        }
+        if !deleted.is_empty() {
+            on_delete(&deleted);
+        }
+    }
+}
+
+fn modified_times(files: Vec<PathBuf>) -> HashMap<PathBuf, SystemTime> {
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let time = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, time))
+        })
+        .collect()
+}
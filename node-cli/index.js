@@ -4,7 +4,7 @@
 const fs = require("fs").promises;
 const path = require("path");
 
-const { parseArgs, outputInfo } = require("./lib");
+const { parseArgs, formatDiagnostic, outputInfo, cacheKey, orphanedEntries } = require("./lib");
 const tr = require("./tr/transform");
 
 /**
@@ -26,49 +26,206 @@ async function writeString(inputPath, outputPath, str, verbose) {
  * @property {() => tr.Options} transformOptions
  */
 
+/**
+ * @param {string} inputPath 
+ * @param {string | undefined | null} inputDir 
+ * @param {string | undefined} outputDir
+ * @param {string | undefined} suffix
+ * @returns {{ dir: string, base: string }} The output directory and path without the extension
+ */
+function outputBase(inputPath, inputDir, outputDir, suffix) {
+    const parsedInput = path.parse(inputPath);
+    let dir, base;
+    if (inputDir && outputDir) {
+        const info = outputInfo(parsedInput, inputDir, outputDir);
+        dir = info.dir;
+        base = path.join(info.dir, info.name);
+    } else {
+        dir = parsedInput.dir;
+        base = path.join(parsedInput.dir, parsedInput.name);
+    }
+    if (suffix) {
+        base += suffix;
+    }
+    return { dir, base };
+}
+
+/**
+ * @template T
+ * @param {string} inputPath 
+ * @param {() => T} fn 
+ * @returns {T}
+ */
+function formattingDiagnostics(inputPath, fn) {
+    try {
+        return fn();
+    } catch (e) {
+        if (Array.isArray(e?.diagnostics)) {
+            throw new Error(
+                e.diagnostics.map((d) => formatDiagnostic(inputPath, d)).join("\n")
+            );
+        }
+        throw e;
+    }
+}
+
 /**
  * @param {Context} ctx 
  * @param {string} inputPath 
  * @param {string | undefined | null} inputDir 
  * @param {string | undefined} outputDir
  * @param {import("./lib").ParsedFlags} flags
- * @returns {Promise<any>}
+ * @param {string} [input] The already read input content
+ * @returns {Promise<string[]>} Written files
  */
-async function transformFile(ctx, inputPath, inputDir, outputDir, { suffix, verbose }) {
-    const parsedInput = path.parse(inputPath);
+async function transformFile(ctx, inputPath, inputDir, outputDir, { suffix, verbose }, input) {
+    const { dir, base } = outputBase(inputPath, inputDir, outputDir, suffix);
+    await fs.mkdir(dir, { recursive: true });
     const timeStart = Date.now();
-    const output = tr.transform(
-        inputPath,
-        await fs.readFile(inputPath, { encoding: "utf-8" }),
+    if (typeof input !== "string") {
+        input = await fs.readFile(inputPath, { encoding: "utf-8" });
+    }
+    const output = formattingDiagnostics(inputPath, () => tr.transform(
+        // Source maps refer to the input relatively to the output
+        path.relative(dir, inputPath),
+        input,
         ctx.transformOptions()
-    );
+    ));
     const timeEnd = Date.now();
     if (verbose) {
+        for (const d of output.diagnostics) {
+            console.warn(formatDiagnostic(inputPath, d));
+        }
         console.log(`Transformed "${inputPath}" in ${timeEnd - timeStart}ms`);
     }
-    let outputPath;
-    if (inputDir && outputDir) {
-        const info = outputInfo(parsedInput, inputDir, outputDir);
-        await fs.mkdir(info.dir, { recursive: true });
-        outputPath = path.join(info.dir);
-    } else {
-        outputPath = path.join(parsedInput.dir, parsedInput.name);
-    }
-    if (suffix) {
-        outputPath += suffix;
+    const outputPath = base + "." + output.ext;
+    const { map } = output;
+    if (map) {
+        const mapPath = outputPath + ".map";
+        await writeString(inputPath, mapPath, map, verbose);
+        await writeString(
+            inputPath,
+            outputPath,
+            output.src + `//# sourceMappingURL=${path.basename(mapPath)}\n`,
+            verbose
+        );
+        return [outputPath, mapPath];
     }
-    return await writeString(inputPath, outputPath + "." + output.ext, output.src, verbose);
+    await writeString(inputPath, outputPath, output.src, verbose);
+    return [outputPath];
 }
 
 /**
+ * Verifies the existing outputs are up to date without writing anything.
  * @param {Context} ctx 
+ * @param {string} inputPath 
+ * @param {string | undefined | null} inputDir 
+ * @param {string | undefined} outputDir
+ * @param {import("./lib").ParsedFlags} flags
+ * @returns {Promise<string[]>} Checked files
+ */
+async function checkFile(ctx, inputPath, inputDir, outputDir, { suffix, sourceMap, verbose }) {
+    const { dir, base } = outputBase(inputPath, inputDir, outputDir, suffix);
+    const ext = tr.outputExt(inputPath);
+    if (!ext) {
+        throw new Error(`Unknown file type at "${inputPath}"`);
+    }
+    const outputPath = base + "." + ext;
+    const mapPath = sourceMap === "separate" ? outputPath + ".map" : null;
+    const input = await fs.readFile(inputPath, { encoding: "utf-8" });
+    let existing = await fs.readFile(outputPath, { encoding: "utf-8" });
+    if (mapPath) {
+        // The separate source map is referred by the output
+        const mapUrl = `//# sourceMappingURL=${path.basename(mapPath)}\n`;
+        if (!existing.endsWith(mapUrl)) {
+            throw new Error(`${outputPath}: no source map reference`);
+        }
+        existing = existing.slice(0, -mapUrl.length);
+    }
+    const check = formattingDiagnostics(inputPath, () => tr.check(
+        path.relative(dir, inputPath),
+        input,
+        ctx.transformOptions(),
+        existing
+    ));
+    if (verbose) {
+        for (const d of check.diagnostics) {
+            console.warn(formatDiagnostic(inputPath, d));
+        }
+    }
+    const { diff, map } = check;
+    if (typeof diff === "string") {
+        throw new Error(`${outputPath} is out of date:\n${diff}`);
+    }
+    if (mapPath && map && await fs.readFile(mapPath, { encoding: "utf-8" }) !== map) {
+        throw new Error(`${mapPath} is out of date`);
+    }
+    if (verbose) {
+        console.log(`"${outputPath}" is up to date`);
+    }
+    return mapPath && map ? [outputPath, mapPath] : [outputPath];
+}
+
+const CACHE_MANIFEST_NAME = ".viewmill-cache.json";
+
+/**
+ * @param {string} manifestPath 
+ * @returns {Promise<import("./lib").CacheManifest>}
+ */
+async function readCacheManifest(manifestPath) {
+    try {
+        const manifest = JSON.parse(await fs.readFile(manifestPath, { encoding: "utf-8" }));
+        if (manifest && typeof manifest.files === "object") {
+            return manifest;
+        }
+    } catch (e) {
+        if (e?.code !== "ENOENT") {
+            console.warn(`Ignoring the invalid cache manifest "${manifestPath}"`);
+        }
+    }
+    return { files: {} };
+}
+
+/**
+ * The manifest is kept in the output directory, or next to the sources without one.
  * @param {string} inputDir 
  * @param {string | undefined} outputDir 
+ * @returns {{ manifestDir: string, manifestPath: string }}
+ */
+function cacheManifestLocation(inputDir, outputDir) {
+    const manifestDir = outputDir ?? inputDir;
+    return { manifestDir, manifestPath: path.join(manifestDir, CACHE_MANIFEST_NAME) };
+}
+
+/**
+ * @param {string} manifestDir 
+ * @param {string} manifestPath 
+ * @param {import("./lib").CacheManifest} manifest 
+ */
+async function writeCacheManifest(manifestDir, manifestPath, manifest) {
+    await fs.mkdir(manifestDir, { recursive: true });
+    await fs.writeFile(manifestPath, JSON.stringify(manifest, null, 2), { encoding: "utf-8" });
+}
+
+/**
+ * @param {string} p 
+ * @returns {Promise<boolean>}
+ */
+async function exists(p) {
+    try {
+        await fs.access(p);
+        return true;
+    } catch {
+        return false;
+    }
+}
+
+/**
+ * @param {string} inputDir 
  * @param {RegExp} re 
- * @param {import("./lib").ParsedFlags} flags 
- * @returns {Promise<any>}
+ * @returns {Promise<string[]>}
  */
-async function transformDir(ctx, inputDir, outputDir, re, flags) {
+async function collectFiles(inputDir, re) {
     const files = [];
     async function walk(/** @type {string} */dir) {
         const list = await fs.readdir(dir);
@@ -83,11 +240,141 @@ async function transformDir(ctx, inputDir, outputDir, re, flags) {
         }
     }
     await walk(inputDir);
-    return Promise.allSettled(
-        files.map((inputPath) => (
-            transformFile(ctx, inputPath, inputDir, outputDir, flags)
-        ))
+    return files;
+}
+
+/**
+ * @param {PromiseSettledResult<any>[]} results 
+ */
+function reportRejected(results) {
+    for (const result of results) {
+        if (result.status === "rejected") {
+            console.error(result.reason?.message ?? result.reason);
+            process.exitCode = 1;
+        }
+    }
+}
+
+/**
+ * @param {Context} ctx 
+ * @param {string} inputDir 
+ * @param {string | undefined} outputDir 
+ * @param {RegExp} re 
+ * @param {import("./lib").ParsedFlags} flags 
+ * @returns {Promise<any>}
+ */
+async function checkDir(ctx, inputDir, outputDir, re, flags) {
+    const files = await collectFiles(inputDir, re);
+    const results = await Promise.allSettled(
+        files.map((inputPath) => checkFile(ctx, inputPath, inputDir, outputDir, flags))
     );
+    reportRejected(results);
+    return results;
+}
+
+/**
+ * @param {Context} ctx 
+ * @param {string} inputDir 
+ * @param {string | undefined} outputDir 
+ * @param {RegExp} re 
+ * @param {import("./lib").ParsedFlags} flags 
+ * @returns {Promise<any>}
+ */
+async function transformDir(ctx, inputDir, outputDir, re, flags) {
+    // Unchanged files are skipped using the manifest
+    const { manifestDir, manifestPath } = cacheManifestLocation(inputDir, outputDir);
+    const manifest = await readCacheManifest(manifestPath);
+    /** @type {import("./lib").CacheManifest} */
+    const nextManifest = { files: {} };
+    const version = tr.version();
+    const files = await collectFiles(inputDir, re);
+    const results = await Promise.allSettled(
+        files.map(async (inputPath) => {
+            const entryName = path.relative(inputDir, inputPath);
+            const input = await fs.readFile(inputPath, { encoding: "utf-8" });
+            const key = cacheKey(input, flags, version);
+            const entry = manifest.files[entryName];
+            if (entry?.key === key) {
+                const outputs = entry.outputs.map((p) => path.join(manifestDir, p));
+                if ((await Promise.all(outputs.map(exists))).every(Boolean)) {
+                    if (flags.verbose) {
+                        console.log(`Skipped unchanged "${inputPath}"`);
+                    }
+                    nextManifest.files[entryName] = entry;
+                    return outputs;
+                }
+            }
+            let outputs;
+            try {
+                outputs = await transformFile(ctx, inputPath, inputDir, outputDir, flags, input);
+            } catch (e) {
+                // The previous outputs are still there, the changed key makes it retried
+                if (entry) {
+                    nextManifest.files[entryName] = entry;
+                }
+                throw e;
+            }
+            nextManifest.files[entryName] = {
+                key,
+                outputs: outputs.map((p) => path.relative(manifestDir, p))
+            };
+            return outputs;
+        })
+    );
+    reportRejected(results);
+    const inputNames = files.map((p) => path.relative(inputDir, p));
+    for (const entryName of orphanedEntries(manifest, inputNames)) {
+        for (const p of manifest.files[entryName].outputs) {
+            const outputPath = path.join(manifestDir, p);
+            if (flags.prune) {
+                await fs.rm(outputPath, { force: true });
+                console.log(`Removed "${outputPath}" as "${entryName}" is deleted`);
+            } else {
+                console.warn(`Orphaned "${outputPath}" as "${entryName}" is deleted (use --prune to remove)`);
+            }
+        }
+        if (!flags.prune) {
+            // Keep reporting until removed
+            nextManifest.files[entryName] = manifest.files[entryName];
+        }
+    }
+    if (files.length > 0 || Object.keys(manifest.files).length > 0) {
+        await writeCacheManifest(manifestDir, manifestPath, nextManifest);
+    }
+    return results;
+}
+
+/**
+ * The pending manifest update of the watched changes, so they don't overwrite each other
+ * @type {Promise<void>}
+ */
+let manifestUpdate = Promise.resolve();
+
+/**
+ * Transforms the changed file of the watched directory updating its manifest entry.
+ * @param {Context} ctx 
+ * @param {string} inputPath 
+ * @param {string} inputDir 
+ * @param {string | undefined} outputDir
+ * @param {import("./lib").ParsedFlags} flags
+ * @returns {Promise<string[]>} Written files
+ */
+async function transformChangedFile(ctx, inputPath, inputDir, outputDir, flags) {
+    const { manifestDir, manifestPath } = cacheManifestLocation(inputDir, outputDir);
+    const input = await fs.readFile(inputPath, { encoding: "utf-8" });
+    const outputs = await transformFile(ctx, inputPath, inputDir, outputDir, flags, input);
+    const update = manifestUpdate.then(async () => {
+        const manifest = await readCacheManifest(manifestPath);
+        manifest.files[path.relative(inputDir, inputPath)] = {
+            key: cacheKey(input, flags, tr.version()),
+            outputs: outputs.map((p) => path.relative(manifestDir, p))
+        };
+        await writeCacheManifest(manifestDir, manifestPath, manifest);
+    });
+    // A failed update doesn't stop the next ones
+    manifestUpdate = update.catch(() => {});
+    await update;
+    return outputs;
 }
 
 const WATCHING_MSG = "\nWatching for changes...\n";
@@ -102,8 +389,10 @@ async function transform(ctx, { inputPath, outputDir }, { re, watch, ...flags })
     const stats = await fs.stat(inputPath);
     if (stats.isDirectory()) {
         const regexp = re ? new RegExp(re) : /\.(jsx|tsx)$/;
+        const processDir = flags.check ? checkDir : transformDir;
+        const processFile = flags.check ? checkFile : transformChangedFile;
         const fn = async () => (
-            await transformDir(ctx, inputPath, outputDir, regexp, flags)
+            await processDir(ctx, inputPath, outputDir, regexp, flags)
         );
         if (watch) {
             await fn();
@@ -111,19 +400,19 @@ async function transform(ctx, { inputPath, outputDir }, { re, watch, ...flags })
             for await (const { filename, eventType } of fs.watch(inputPath, { recursive: true })) {
                 if (eventType === "change" && filename && regexp.test(filename)) {
                     const p = path.join(inputPath, filename);
-                    try {
-                        await transformFile(ctx, p, inputPath, outputDir, flags);
-                    } catch (e) {
+                    // Not awaited to catch the next events, the manifest updates are queued
+                    processFile(ctx, p, inputPath, outputDir, flags).catch((e) => {
                         console.error(e);
-                    }
+                    });
                 }
             }
         } else {
             return await fn();
         }
     } else if (stats.isFile()) {
+        const processFile = flags.check ? checkFile : transformFile;
         const fn = async () => (
-            await transformFile(ctx, inputPath, null, outputDir, flags)
+            await processFile(ctx, inputPath, null, outputDir, flags)
         );
         if (watch) {
             await fn();
@@ -134,7 +423,12 @@ async function transform(ctx, { inputPath, outputDir }, { re, watch, ...flags })
                 }
             }
         } else {
-            return await fn();
+            try {
+                return await fn();
+            } catch (e) {
+                console.error(e?.message ?? e);
+                process.exitCode = 1;
+            }
         }
     }
 }
@@ -142,17 +436,24 @@ async function transform(ctx, { inputPath, outputDir }, { re, watch, ...flags })
 const USAGE_TEXT = `Usage: viewmill [OPTIONS] INPUT_PATH [OUTPUT_DIR]
 
 Arguments:
-  INPUT_PATH    File or directory. By default searches for all *.{jsx,tsx} files in a directory. Use the \`--re\` option to set your own filter.
-  [OUTPUT_DIR]  Output directory
+  INPUT_PATH       File or directory. By default searches for all *.{jsx,tsx} files in a directory. Use the \`--re\` option to set your own filter.
+  [OUTPUT_DIR]     Output directory
 
 Options:
-  --re          A regular expression to filter files in a directory (see the examples)
-  --target      Output js target: ${tr.displayEsVersions()}
-  --suffix      How to suffix output file names
-  --verbose     Shows warnings and files as they are transformed
-  --watch       Starts watching for changes
-  --help        Prints this message
-  --version     Prints the current version
+  --re             A regular expression to filter files in a directory (see the examples)
+  --target         Output js target: ${tr.displayEsVersions()}
+  --suffix         How to suffix output file names
+  --source-map     Generates source maps: "inline" or "separate" (as *.map files)
+  --named-views    Which named exports are views besides the default one: "none" (by default), "capitalized" or "marked" (by the \`/* @view */\` comment)
+  --runtime        The runtime module specifier, "viewmill-runtime" by default
+  --import-style   How to import the runtime: "namespace" (by default), "named" (only the used helpers) or "require" (CommonJS)
+  --mode           What views are transformed into: "dom" (by default), "ssr" (rendering HTML strings), "hydrate" (adopting server-rendered DOM) or "element" (defining custom elements)
+  --verbose        Shows warnings and files as they are transformed
+  --watch          Starts watching for changes
+  --check          Verifies the existing outputs are up to date without writing anything
+  --prune          Removes outputs of deleted sources, which are only reported by default
+  --help           Prints this message
+  --version        Prints the current version
 
 Examples:
   1. Transform file:
@@ -162,7 +463,9 @@ Examples:
   3. Set target:
         viewmill --target esnext path/to/smth
   4. Suffix to get "*-view.js" as an output for "*.jsx":
-        viewmill --suffix "-view" path/to/dir`;
+        viewmill --suffix "-view" path/to/dir
+  5. Fail if the generated files are outdated, e.g. on CI:
+        viewmill --check --suffix "-view" path/to/dir`;
 
 /**
  * @param {string} [prefix]
@@ -204,8 +507,8 @@ async function main(args) {
             /** @type {Context} */
             const ctx = {
                 transformOptions() {
-                    const { target, verbose } = flags;
-                    return new tr.Options(target, verbose);
+                    const { target, verbose, sourceMap, namedViews, runtime, importStyle, mode } = flags;
+                    return new tr.Options(target, verbose, sourceMap, namedViews, runtime, importStyle, mode);
                 }
             };
             await transform(ctx, params, flags);
@@ -4,8 +4,15 @@ export type ParsedFlags = {
     re?: string,
     target?: string,
     suffix?: string,
+    sourceMap?: string,
+    namedViews?: string,
+    runtime?: string,
+    importStyle?: string,
+    mode?: string,
     verbose?: boolean,
+    check?: boolean,
     watch?: boolean,
+    prune?: boolean,
     showHelp?: boolean,
     showVersion?: boolean
 }
@@ -22,7 +29,37 @@ export type ParsedArgs = {
 
 export declare function parseArgs(args: string[], offset?: number): ParsedArgs;
 
+export type Diagnostic = {
+    severity: string,
+    message: string,
+    code?: string,
+    startLine?: number,
+    startColumn?: number
+};
+
+export declare function formatDiagnostic(inputPath: string, diagnostic: Diagnostic): string;
+
 export declare function outputInfo(parsedPath: ParsedPath, inputDir: string, outputDir: string): {
     dir: string,
     name: string
 };
+
+
+export type CacheManifest = {
+    /** Input paths relative to the input directory */
+    files: Record<string, {
+        key: string,
+        /** Output paths relative to the manifest directory */
+        outputs: string[]
+    }>
+};
+
+/**
+ * Hashes the input content along with the output affecting flags and the transformer version
+ */
+export declare function cacheKey(input: string, flags: ParsedFlags, version: string): string;
+
+/**
+ * Manifest entries, which sources are deleted
+ */
+export declare function orphanedEntries(manifest: CacheManifest, inputPaths: string[]): string[];
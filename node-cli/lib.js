@@ -1,7 +1,11 @@
 // @ts-check
 
+const crypto = require("crypto");
 const path = require("path");
 
+/** Flags affecting the transformation output */
+const OUTPUT_FLAGS = ["target", "suffix", "sourceMap", "namedViews", "runtime", "importStyle", "mode"];
+
 /** @type {import("./lib")} */
 module.exports = {
     parseArgs: function (args, offset = 0) {
@@ -23,11 +27,46 @@ module.exports = {
                 i += 2;
                 continue;
             }
+            if (args[i] === "--source-map") {
+                flags.sourceMap = args[i + 1];
+                i += 2;
+                continue;
+            }
+            if (args[i] === "--named-views") {
+                flags.namedViews = args[i + 1];
+                i += 2;
+                continue;
+            }
+            if (args[i] === "--runtime") {
+                flags.runtime = args[i + 1];
+                i += 2;
+                continue;
+            }
+            if (args[i] === "--import-style") {
+                flags.importStyle = args[i + 1];
+                i += 2;
+                continue;
+            }
+            if (args[i] === "--mode") {
+                flags.mode = args[i + 1];
+                i += 2;
+                continue;
+            }
             if (args[i] === "--verbose") {
                 flags.verbose = true;
                 i += 1;
                 continue;
             }
+            if (args[i] === "--prune") {
+                flags.prune = true;
+                i += 1;
+                continue;
+            }
+            if (args[i] === "--check") {
+                flags.check = true;
+                i += 1;
+                continue;
+            }
             if (args[i] === "--watch") {
                 flags.watch = true;
                 i += 1;
@@ -53,11 +92,30 @@ module.exports = {
             }
         };
     },
+    formatDiagnostic(inputPath, { severity, code, message, startLine, startColumn }) {
+        let prefix = inputPath;
+        if (typeof startLine === "number" && typeof startColumn === "number") {
+            prefix += `:${startLine}:${startColumn + 1}`;
+        }
+        return `${prefix}: ${severity}${code ? `[${code}]` : ""}: ${message}`;
+    },
     outputInfo(parsedPath, inputDir, outputDir) {
         const subdir = parsedPath.dir.substring(inputDir.length);
         return {
             dir: path.join(outputDir, subdir),
             name: parsedPath.name
         };
+    },
+    cacheKey(input, flags, version) {
+        const options = OUTPUT_FLAGS.map((k) => flags[k] ?? null);
+        return crypto
+            .createHash("sha256")
+            .update(JSON.stringify({ version, options }))
+            .update(input)
+            .digest("hex");
+    },
+    orphanedEntries(manifest, inputPaths) {
+        const inputs = new Set(inputPaths);
+        return Object.keys(manifest.files).filter((p) => !inputs.has(p));
     }
 };
\ No newline at end of file
@@ -1,6 +1,9 @@
 // @ts-check
 
-const { parseArgs, outputInfo } = require("./lib");
+const { parseArgs, formatDiagnostic, outputInfo, cacheKey, orphanedEntries } = require("./lib");
+const { spawnSync } = require("child_process");
+const fs = require("fs");
+const os = require("os");
 const path = require("path");
 
 /**
@@ -56,8 +59,17 @@ const cases = [
     () => assertObjEq(parseArgs(["--re", ".*"]).flags, { re: ".*" }),
     () => assertObjEq(parseArgs(["--target", "es2020"]).flags, { target: "es2020" }),
     () => assertObjEq(parseArgs(["--suffix", ".view"]).flags, { suffix: ".view" }),
+    () => assertObjEq(parseArgs(["--source-map", "inline"]).flags, { sourceMap: "inline" }),
+    () => assertObjEq(parseArgs(["--named-views", "marked"]).flags, { namedViews: "marked" }),
+    () => assertObjEq(
+        parseArgs(["--runtime", "./runtime.js", "--import-style", "named"]).flags,
+        { runtime: "./runtime.js", importStyle: "named" }
+    ),
+    () => assertObjEq(parseArgs(["--mode", "ssr"]).flags, { mode: "ssr" }),
     () => assertObjEq(parseArgs(["--verbose"]).flags, { verbose: true }),
+    () => assertObjEq(parseArgs(["--check"]).flags, { check: true }),
     () => assertObjEq(parseArgs(["--watch"]).flags, { watch: true }),
+    () => assertObjEq(parseArgs(["--prune"]).flags, { prune: true }),
     () => assertObjEq(parseArgs(["--help"]).flags, { showHelp: true }),
     () => assertObjEq(parseArgs(["--version"]).flags, { showVersion: true }),
     () => {
@@ -84,6 +96,17 @@ const cases = [
         });
     },
     //
+    // Format diagnostic
+    //
+    () => assertStrictEq(
+        formatDiagnostic("a.jsx", { severity: "error", code: "syntax", message: "Oops", startLine: 2, startColumn: 4 }),
+        "a.jsx:2:5: error[syntax]: Oops"
+    ),
+    () => assertStrictEq(
+        formatDiagnostic("a.jsx", { severity: "warning", message: "Oops" }),
+        "a.jsx: warning: Oops"
+    ),
+    //
     // Output info
     //
     () => {
@@ -101,8 +124,62 @@ const cases = [
         assertStrictEq(o.dir, "out/foo/bar/a/b/c");
         assertStrictEq(o.name, "file");
     },
+    //
+    // Cache
+    //
+    () => {
+        const key = cacheKey("<p/>", { mode: "ssr" }, "0.1.0");
+        assertStrictEq(key, cacheKey("<p/>", { mode: "ssr", verbose: true, watch: true }, "0.1.0"));
+        assert(key !== cacheKey("<p/>", { mode: "ssr" }, "0.2.0"));
+        assert(key !== cacheKey("<p/>", { mode: "dom" }, "0.1.0"));
+        assert(key !== cacheKey("<i/>", { mode: "ssr" }, "0.1.0"));
+    },
+    () => {
+        const manifest = {
+            files: {
+                "a.jsx": { key: "", outputs: ["a.js"] },
+                "b/c.jsx": { key: "", outputs: ["b/c.js"] }
+            }
+        };
+        const orphans = orphanedEntries(manifest, ["a.jsx"]);
+        assertStrictEq(orphans.length, 1);
+        assertStrictEq(orphans[0], "b/c.jsx");
+    },
 ];
 
+/**
+ * @param {string[]} args
+ */
+function runCli(...args) {
+    return spawnSync(process.execPath, [path.join(__dirname, "index.js"), ...args], {
+        encoding: "utf-8"
+    });
+}
+
+// The CLI itself requires the wasm build (see `npm run build`)
+if (fs.existsSync(path.join(__dirname, "tr"))) {
+    cases.push(
+        () => {
+            const dir = fs.mkdtempSync(path.join(os.tmpdir(), "viewmill-"));
+            try {
+                fs.writeFileSync(path.join(dir, "a.jsx"), "export default () => <p>Hi</p>;\n");
+                assertStrictEq(runCli(dir).status, 0);
+                assertStrictEq(runCli("--check", dir).status, 0);
+                fs.appendFileSync(path.join(dir, "a.js"), "// edited\n");
+                const r = runCli("--check", dir);
+                assertStrictEq(r.status, 1);
+                assert(r.stderr.includes("is out of date"), r.stderr);
+                fs.rmSync(path.join(dir, "a.js"));
+                assertStrictEq(runCli("--check", path.join(dir, "a.jsx")).status, 1);
+            } finally {
+                fs.rmSync(dir, { recursive: true, force: true });
+            }
+        }
+    );
+} else {
+    console.warn("Skipping the CLI tests without the wasm build");
+}
+
 console.log(`Running ${cases.length} tests...`);
 cases.forEach((fn) => fn());
 console.log("Passed!");
\ No newline at end of file
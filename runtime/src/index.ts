@@ -1,23 +1,60 @@
+/**
+ * Listener options lowered from event modifiers like `on:click_prevent_once`
+ */
+export type ListenOptions = {
+    prevent?: boolean,
+    stop?: boolean,
+    once?: boolean,
+    passive?: boolean,
+    capture?: boolean,
+};
+
+function withModifiers(cb: EventListener | null, options?: ListenOptions | null): EventListener | null {
+    const prevent = options?.prevent;
+    const stop = options?.stop;
+    if (cb && (prevent || stop)) {
+        const handle = cb;
+        return function (this: unknown, e: Event) {
+            if (prevent) {
+                e.preventDefault();
+            }
+            if (stop) {
+                e.stopPropagation();
+            }
+            return handle.call(this, e);
+        };
+    }
+    return cb;
+}
+
 export function listen(
     target: EventTarget,
     eventName: string,
     cb: EventListener | null,
     deps?: Live<unknown>[] | null,
-    signal?: AbortSignal,
+    signal?: AbortSignal | null,
+    options?: ListenOptions | null,
 ) {
+    const listener = withModifiers(cb, options);
+    const listenerOptions: AddEventListenerOptions = {
+        signal: signal ?? undefined,
+        once: options?.once,
+        passive: options?.passive,
+        capture: options?.capture,
+    };
     if (deps && deps.length > 0) {
         let currentListener: EventListener | null = null;
         const update = () => {
             if (currentListener) {
-                target.removeEventListener(eventName, currentListener);
+                target.removeEventListener(eventName, currentListener, listenerOptions);
             }
-            target.addEventListener(eventName, cb, { signal });
-            currentListener = cb;
+            target.addEventListener(eventName, listener, listenerOptions);
+            currentListener = listener;
         };
-        listenDeps(deps, update, signal);
+        listenDeps(deps, update, signal ?? undefined);
         update();
     } else {
-        target.addEventListener(eventName, cb, { signal });
+        target.addEventListener(eventName, listener, listenerOptions);
     }
 }
 
@@ -62,17 +99,49 @@ function listenDeps(
     }
 }
 
+export type Comparator<T> = (a: T, b: T) => boolean;
+
+/**
+ * Checks if both values are the same or arrays/plain objects with the same items
+ */
+export function shallowEqual(a: unknown, b: unknown): boolean {
+    if (a === b) {
+        return true;
+    }
+    if (Array.isArray(a) && Array.isArray(b)) {
+        return a.length === b.length && a.every((v, i) => v === b[i]);
+    }
+    if (isPlainObject(a) && isPlainObject(b)) {
+        const keys = Object.keys(a);
+        return keys.length === Object.keys(b).length
+            && keys.every((k) => Object.prototype.hasOwnProperty.call(b, k) && a[k] === b[k]);
+    }
+    return false;
+}
+
+function isPlainObject(v: unknown): v is Record<string, unknown> {
+    if (v === null || typeof v !== "object") {
+        return false;
+    }
+    const proto = Object.getPrototypeOf(v);
+    return proto === Object.prototype || proto === null;
+}
+
 class ReadonlyLive<T> extends Live<T> {
 
     public constructor(
         readValue: () => T,
         deps?: Live<unknown>[] | null,
-        signal?: AbortSignal
+        signal?: AbortSignal,
+        compare?: Comparator<T>
     ) {
         super(readValue());
         listenDeps(deps, () => {
             const newValue = readValue();
-            if (this.currentValue !== newValue) {
+            const equals = compare
+                ? compare(this.currentValue, newValue)
+                : this.currentValue === newValue;
+            if (!equals) {
                 const oldValue = this.currentValue;
                 this.currentValue = newValue;
                 this.emit({ newValue, oldValue });
@@ -86,18 +155,21 @@ export function live<T>(
     deps?: Live<unknown>[] | null,
     destruct?: null,
     signal?: AbortSignal,
+    compare?: Comparator<T>,
 ): Live<T>;
 export function live<T>(
     readValue: () => T,
     deps: Live<unknown>[] | null,
     destruct: [number, (value: T) => unknown[]],
     signal?: AbortSignal,
+    compare?: Comparator<unknown>,
 ): Live<unknown>[];
 export function live<T>(
     readValue: () => T,
     deps?: Live<unknown>[] | null,
     destruct?: [number, (value: T) => unknown[]] | null,
     signal?: AbortSignal,
+    compare?: Comparator<any>,
 ): Live<T> | Live<unknown>[] {
     if (destruct) {
         const [count, fn] = destruct;
@@ -105,19 +177,80 @@ export function live<T>(
             new ReadonlyLive(
                 () => fn(readValue())[i],
                 deps,
-                signal
+                signal,
+                compare
             )
         ));
     } else {
-        return new ReadonlyLive(readValue, deps, signal);
+        return new ReadonlyLive(readValue, deps, signal, compare);
     }
 }
 
+/**
+ * Runs the side effect now and every time its dependencies change until the signal is aborted
+ */
+export function effect(
+    run: () => unknown,
+    deps: Live<unknown>[] | null,
+    signal?: AbortSignal
+) {
+    if (signal?.aborted) {
+        return;
+    }
+    run();
+    listenDeps(deps, () => run(), signal);
+}
+
+function select(value: unknown, path: string[]): unknown {
+    let v: any = value;
+    for (const key of path) {
+        if (v === null || typeof v === "undefined") {
+            return undefined;
+        }
+        v = v[key];
+    }
+    return v;
+}
+
+class MemberLive<T> extends Live<T> {
+
+    public constructor(
+        private source: Live<unknown>,
+        private path: string[]
+    ) {
+        super(select(source.getValue(), path) as T);
+    }
+
+    getValue(): T {
+        return select(this.source.getValue(), this.path) as T;
+    }
+
+    public listen<V extends ChangeEventValue<T>>(
+        cb: (v: V) => void,
+        signal?: AbortSignal
+    ) {
+        this.source.listen(({ newValue, oldValue, userData }) => {
+            const n = select(newValue, this.path) as T;
+            const o = select(oldValue, this.path) as T;
+            if (n !== o) {
+                cb({ newValue: n, oldValue: o, userData } as V);
+            }
+        }, signal);
+    }
+}
+
+/**
+ * The member of the live value, e.g. `user.name`, which only changes if the member does
+ */
+export function member<T>(source: Live<unknown>, ...path: string[]): Live<T> {
+    return new MemberLive(source, path);
+}
+
 // Param
 
 export class Param<T> extends Live<T> {
 
-    private compare: ((a: T, b: T) => boolean) | undefined;
+    private compare: Comparator<T> | undefined;
 
     setValue(newValue: T, userData?: unknown) {
         const equals = this.compare
@@ -134,7 +267,7 @@ export class Param<T> extends Live<T> {
         this.setValue(update(this.currentValue), userData);
     }
 
-    setComparator(cmp: (a: T, b: T) => boolean) {
+    setComparator(cmp: Comparator<T>) {
         this.compare = cmp;
     }
 }
@@ -143,6 +276,23 @@ export function param<T>(initial: T): Param<T> {
     return new Param(initial);
 }
 
+// State
+
+export type Setter<T> = (value: T) => void;
+
+/**
+ * Declares a local state of a view like `const [open, setOpen] = state(false)`.
+ * Views get a live value instead (see `localState`), but rendered ones just the initial value.
+ */
+export function state<T>(initial: T): [T, Setter<T>] {
+    return [initial, () => { }];
+}
+
+export function localState<T>(initial: T): [Param<T>, Setter<T>] {
+    const value = param(initial);
+    return [value, (v) => value.setValue(v)];
+}
+
 // Unmount
 
 export type Unmounter = (removing: boolean) => void;
@@ -177,6 +327,17 @@ export function insert(input: Insertable, target: Node, anchor: Node | null = nu
     if (anchor && anchor.parentNode !== target) {
         return null;
     }
+    if (anchor && isMarker(anchor, MARKER_END)) {
+        // Returned by `anchor` while hydrating
+        const start = startMarkerOf(anchor);
+        if (start) {
+            return hydrateRange(input, target, start, anchor);
+        }
+    }
+    return insertNodes(input, target, anchor);
+}
+
+function insertNodes(input: Insertable, target: Node, anchor: Node | null): Unmounter | null {
     if (input === null || typeof input === "undefined") {
         return null;
     } else if (input instanceof Insertion) {
@@ -192,7 +353,7 @@ export function insert(input: Insertable, target: Node, anchor: Node | null = nu
         const list: (Unmounter | null)[] = [];
         for (const entry of input) {
             list.push(
-                insert(entry, target, anchor)
+                insertNodes(entry, target, anchor)
             );
         }
         return (removing) => list.forEach((u) => u?.(removing));
@@ -210,7 +371,7 @@ function insertFragment(input: DocumentFragment, target: Node, anchor: Node | nu
     if (len === 0) {
         return null;
     } else if (len === 1) {
-        return insert(input.firstChild, target, anchor);
+        return insertNodes(input.firstChild, target, anchor);
     } else {
         const end = target.insertBefore(document.createComment("frag:end"), anchor);
         const start = target.insertBefore(document.createComment("frag:start"), end);
@@ -242,12 +403,58 @@ export class NodeSpan {
         this.start = target.insertBefore(document.createComment(name + ":start"), this.end);
     }
 
+    /**
+     * Creates a span around the existing children of the target.
+     */
+    public static around(target: Node, name: string = "span"): NodeSpan {
+        const first = target.firstChild;
+        const span = new NodeSpan(target, null, name);
+        target.insertBefore(span.start, first);
+        return span;
+    }
+
     public append(input: Insertable) {
         this.unmounters.push(
             insert(input, this.container, this.end)
         );
     }
 
+    /**
+     * Creates a span around the nodes adopted from the cursor, unless they
+     * don't match the input.
+     */
+    public static adopting(
+        input: Insertable,
+        target: Node,
+        cursor: Node | null,
+        name: string = "span"
+    ): [NodeSpan, Node | null] | null {
+        const span = new NodeSpan(target, cursor, name);
+        const adopted = adoptNodes(input, target, cursor);
+        if (!adopted) {
+            span.unmount(true);
+            return null;
+        }
+        const [un, next] = adopted;
+        target.insertBefore(span.end, next);
+        span.unmounters.push(un);
+        return [span, next];
+    }
+
+    /**
+     * Adopts the nodes between the span markers instead of inserting new ones.
+     * Returns false, if they don't match the input.
+     */
+    public adopt(input: Insertable): boolean {
+        const adopted = adoptNodes(input, this.container, this.start.nextSibling);
+        if (!adopted || skipWhitespace(adopted[1]) !== this.end) {
+            adopted?.[0]?.(false);
+            return false;
+        }
+        this.unmounters.push(adopted[0]);
+        return true;
+    }
+
     public *elementGenerator(): Generator<Element> {
         let current: Node | null | undefined = this.start;
         while (current && current !== this.end) {
@@ -258,6 +465,21 @@ export class NodeSpan {
         }
     }
 
+    public get firstNode(): Node {
+        return this.start;
+    }
+
+    public get lastNode(): Node {
+        return this.end;
+    }
+
+    public moveBefore(anchor: Node | null) {
+        const range = document.createRange();
+        range.setStartBefore(this.start);
+        range.setEndAfter(this.end);
+        this.container.insertBefore(range.extractContents(), anchor);
+    }
+
     private unmountContents() {
         this.unmounters.forEach((u) => u?.(false));
         this.unmounters = [];
@@ -286,10 +508,8 @@ export function list<T extends Iterable<Insertable>>(
     input: () => T,
     deps?: Live<unknown>[]
 ): Insertable {
-    return new Insertion((target, anchor) => {
+    const mount = (span: NodeSpan): Unmounter => {
         let abortController: AbortController | undefined;
-        const span = new NodeSpan(target, anchor);
-        span.append(input());
         if (deps && deps.length > 0) {
             const update = () => {
                 span.clear();
@@ -303,7 +523,166 @@ export function list<T extends Iterable<Insertable>>(
             abortController?.abort();
             span.unmount(removing);
         };
-    });
+    };
+    return new Adoption(
+        (target, anchor) => {
+            const span = new NodeSpan(target, anchor);
+            span.append(input());
+            return mount(span);
+        },
+        (target, cursor) => {
+            const adopted = NodeSpan.adopting(input(), target, cursor);
+            return adopted && [mount(adopted[0]), adopted[1]];
+        }
+    );
+}
+
+/**
+ * Renders an item once per key, so on updates the existing items are only
+ * moved or removed. An item keeping its key is re-rendered only if it's
+ * another value, e.g. a replaced immutable object.
+ */
+export function keyed<T, K>(
+    items: () => Iterable<T>,
+    key: (item: T, index: number) => K,
+    render: (item: T, index: number) => Insertable,
+    deps?: Live<unknown>[]
+): Insertable {
+    const mount = (
+        target: Node,
+        start: Node,
+        end: Node,
+        adopted: [K, NodeSpan, T][] | null
+    ): Unmounter => {
+        let entries = adopted ?? [];
+        const update = () => {
+            const prev = new Map<K, [NodeSpan, T][]>();
+            for (const [k, span, item] of entries) {
+                const list = prev.get(k);
+                if (list) {
+                    list.push([span, item]);
+                } else {
+                    prev.set(k, [[span, item]]);
+                }
+            }
+            const next: [K, NodeSpan, T][] = [];
+            let index = 0;
+            for (const item of items()) {
+                const k = key(item, index);
+                const kept = prev.get(k)?.shift();
+                let span: NodeSpan;
+                if (kept) {
+                    span = kept[0];
+                    if (kept[1] !== item) {
+                        span.clear();
+                        span.append(render(item, index));
+                    }
+                } else {
+                    span = new NodeSpan(target, end, "item");
+                    span.append(render(item, index));
+                }
+                next.push([k, span, item]);
+                index += 1;
+            }
+            prev.forEach((list) => list.forEach(([span]) => span.unmount(true)));
+            let cursor = start.nextSibling;
+            for (const [, span] of next) {
+                if (span.firstNode === cursor) {
+                    cursor = span.lastNode.nextSibling;
+                } else {
+                    span.moveBefore(cursor);
+                }
+            }
+            entries = next;
+        };
+        if (!adopted) {
+            update();
+        }
+        let abortController: AbortController | undefined;
+        if (deps && deps.length > 0) {
+            const ac = new AbortController();
+            listenDeps(deps, update, ac.signal);
+            abortController = ac;
+        }
+        return (removing) => {
+            abortController?.abort();
+            entries.forEach(([, span]) => span.unmount(removing));
+            entries = [];
+            if (removing) {
+                target.removeChild(start);
+                target.removeChild(end);
+            }
+        };
+    };
+    return new Adoption(
+        (target, anchor) => {
+            const end = target.insertBefore(document.createComment("keyed:end"), anchor);
+            const start = target.insertBefore(document.createComment("keyed:start"), end);
+            return mount(target, start, end, null);
+        },
+        (target, cursor) => {
+            const start = target.insertBefore(document.createComment("keyed:start"), cursor);
+            const entries: [K, NodeSpan, T][] = [];
+            let index = 0;
+            for (const item of items()) {
+                const adopted = NodeSpan.adopting(render(item, index), target, cursor, "item");
+                if (!adopted) {
+                    entries.forEach(([, span]) => span.unmount(false));
+                    return null;
+                }
+                entries.push([key(item, index), adopted[0], item]);
+                cursor = adopted[1];
+                index += 1;
+            }
+            const end = target.insertBefore(document.createComment("keyed:end"), cursor);
+            return [mount(target, start, end, entries), cursor];
+        }
+    );
+}
+
+/**
+ * Inserts the input again before its comment on every change.
+ */
+function reinsert(
+    name: string,
+    input: () => Insertable,
+    deps: Live<unknown>[]
+): Insertable {
+    const mount = (target: Node, a: Node, un: Unmounter | null): Unmounter => {
+        const abortController = new AbortController();
+        const update = () => {
+            un?.(true);
+            un = insert(input(), target, a);
+        };
+        listenDeps(deps, update, abortController.signal);
+        return (removing) => {
+            abortController.abort();
+            un?.(removing);
+            if (removing) {
+                target.removeChild(a);
+            }
+        };
+    };
+    return new Adoption(
+        (target, anchor) => {
+            const a = target.insertBefore(
+                document.createComment(name),
+                anchor
+            );
+            return mount(target, a, insert(input(), target, a));
+        },
+        (target, cursor) => {
+            const adopted = adoptNodes(input(), target, cursor);
+            if (!adopted) {
+                return null;
+            }
+            const a = target.insertBefore(
+                document.createComment(name),
+                adopted[1]
+            );
+            return [mount(target, a, adopted[0]), adopted[1]];
+        }
+    );
 }
 
 export function cond(
@@ -313,27 +692,7 @@ export function cond(
     deps?: Live<unknown>[]
 ): Insertable {
     if (deps && deps.length > 0) {
-        return new Insertion((target, anchor) => {
-            const a = target.insertBefore(
-                document.createComment("cond"),
-                anchor
-            );
-            const abortController = new AbortController();
-            let un: Unmounter | null = null;
-            const update = () => {
-                un?.(true);
-                un = insert(test() ? cons() : alt(), target, a);
-            };
-            listenDeps(deps, update, abortController.signal);
-            update();
-            return (removing) => {
-                abortController.abort();
-                un?.(removing);
-                if (removing) {
-                    target.removeChild(a);
-                }
-            };
-        });
+        return reinsert("cond", () => test() ? cons() : alt(), deps);
     } else {
         return test() ? cons() : alt();
     }
@@ -344,29 +703,48 @@ export function expr(
     deps?: Live<unknown>[]
 ): Insertable {
     if (deps && deps.length > 0) {
-        return new Insertion((target, anchor) => {
-            const a = target.insertBefore(
-                document.createComment("expr"),
-                anchor
-            );
+        return reinsert("expr", input, deps);
+    } else {
+        return input();
+    }
+}
+
+/**
+ * Inserts a single text node and updates its data in place, so primitive
+ * values don't recreate nodes on every change.
+ */
+export function text(
+    input: () => unknown,
+    deps?: Live<unknown>[]
+): Insertable {
+    const toData = (v: unknown) => (
+        v === null || typeof v === "undefined" ? "" : String(v)
+    );
+    if (deps && deps.length > 0) {
+        const mount = (target: Node, txt: Text): Unmounter => {
             const abortController = new AbortController();
-            let un: Unmounter | null = null;
-            const update = () => {
-                un?.(true);
-                un = insert(input(), target, a);
-            };
-            listenDeps(deps, update, abortController.signal);
-            update();
+            listenDeps(deps, () => {
+                txt.data = toData(input());
+            }, abortController.signal);
             return (removing) => {
                 abortController.abort();
-                un?.(removing);
                 if (removing) {
-                    target.removeChild(a);
+                    target.removeChild(txt);
                 }
             };
-        });
+        };
+        return new Adoption(
+            (target, anchor) => mount(
+                target,
+                target.insertBefore(document.createTextNode(toData(input())), anchor)
+            ),
+            (target, cursor) => {
+                const adopted = adoptText(toData(input()), target, cursor);
+                return adopted && [mount(target, adopted[0]), adopted[1]];
+            }
+        );
     } else {
-        return input();
+        return input() as Insertable;
     }
 }
 
@@ -400,6 +778,30 @@ export function attr(
     }
 }
 
+/**
+ * Sets a DOM property, so the value is reflected even after user interaction.
+ * The value is read via a function if there are dependencies.
+ */
+export function prop(
+    el: Element,
+    name: string,
+    value: unknown,
+    deps?: Live<unknown>[],
+    signal?: AbortSignal
+) {
+    const target = el as unknown as Record<string, unknown>;
+    if (deps) {
+        const read = value as () => unknown;
+        const update = () => {
+            target[name] = read();
+        };
+        listenDeps(deps, update, signal);
+        update();
+    } else {
+        target[name] = value;
+    }
+}
+
 export function attrs(
     el: Element,
     values: (() => Record<string, string | boolean>) | Record<string, string | boolean>,
@@ -419,15 +821,229 @@ export function attrs(
     }
 }
 
+/**
+ * Toggles the class token, so the rest of classes are kept, e.g. added by
+ * third-party code. The value is read via a function if there are dependencies.
+ */
+export function toggleClass(
+    el: Element,
+    token: string,
+    value: unknown,
+    deps?: Live<unknown>[],
+    signal?: AbortSignal
+) {
+    if (deps) {
+        const read = value as () => unknown;
+        const update = () => {
+            el.classList.toggle(token, !!read());
+        };
+        listenDeps(deps, update, signal);
+        update();
+    } else {
+        el.classList.toggle(token, !!value);
+    }
+}
+
+function updateStyle(el: Element, name: string, value: unknown) {
+    const { style } = el as HTMLElement;
+    if (value === null || typeof value === "undefined" || value === false) {
+        style.removeProperty(name);
+    } else {
+        style.setProperty(name, String(value));
+    }
+}
+
+/**
+ * Sets the single style property, which is removed if the value is `null`,
+ * `undefined` or `false`. The value is read via a function if there are dependencies.
+ */
+export function style(
+    el: Element,
+    name: string,
+    value: unknown,
+    deps?: Live<unknown>[],
+    signal?: AbortSignal
+) {
+    if (deps) {
+        const read = value as () => unknown;
+        const update = () => {
+            updateStyle(el, name, read());
+        };
+        listenDeps(deps, update, signal);
+        update();
+    } else {
+        updateStyle(el, name, value);
+    }
+}
+
+function classTokens(value: unknown): string[] {
+    if (value === null || typeof value === "undefined" || value === false) {
+        return [];
+    }
+    return String(value).split(/\s+/).filter((t) => t.length > 0);
+}
+
+/**
+ * Adds the class tokens of the value removing the previous ones, so the tokens
+ * toggled by the `class:` directives are kept. The value is read via a function
+ * if there are dependencies.
+ */
+export function mergeClass(
+    el: Element,
+    value: unknown,
+    deps?: Live<unknown>[],
+    signal?: AbortSignal
+) {
+    let prev: string[] = [];
+    const update = (value: unknown) => {
+        const next = classTokens(value);
+        prev.forEach((t) => {
+            if (!next.includes(t)) {
+                el.classList.remove(t);
+            }
+        });
+        next.forEach((t) => el.classList.add(t));
+        prev = next;
+    };
+    if (deps) {
+        const read = value as () => unknown;
+        listenDeps(deps, () => update(read()), signal);
+        update(read());
+    } else {
+        update(value);
+    }
+}
+
+/** `[name, value]` pairs of the raw declarations like `"color: red; margin: 0"` */
+function styleDeclarations(value: unknown): [string, string][] {
+    const result: [string, string][] = [];
+    if (typeof value === "string") {
+        value.split(";").forEach((decl) => {
+            const idx = decl.indexOf(":");
+            if (idx > 0) {
+                result.push([decl.slice(0, idx).trim(), decl.slice(idx + 1).trim()]);
+            }
+        });
+    }
+    return result;
+}
+
+/**
+ * Sets the style declarations of the value removing the previous ones, so the
+ * properties set by the `style:` directives are kept. The value is read via a
+ * function if there are dependencies.
+ */
+export function mergeStyle(
+    el: Element,
+    value: unknown,
+    deps?: Live<unknown>[],
+    signal?: AbortSignal
+) {
+    let prev: string[] = [];
+    const update = (value: unknown) => {
+        const next = styleDeclarations(value);
+        const names = next.map(([name]) => name);
+        prev.forEach((name) => {
+            if (!names.includes(name)) {
+                updateStyle(el, name, null);
+            }
+        });
+        next.forEach(([name, value]) => updateStyle(el, name, value));
+        prev = names;
+    };
+    if (deps) {
+        const read = value as () => unknown;
+        listenDeps(deps, () => update(read()), signal);
+        update(read());
+    } else {
+        update(value);
+    }
+}
+
+export type RefObject<T> = { current: T | null };
+
+export type Ref<T> = RefObject<T> | ((node: T | null) => void);
+
+export function createRef<T extends Element = Element>(): RefObject<T> {
+    return { current: null };
+}
+
+function assignRef<T>(target: Ref<T> | null | undefined, node: T | null) {
+    if (typeof target === "function") {
+        target(node);
+    } else if (target) {
+        target.current = node;
+    }
+}
+
+/**
+ * Passes the element to the ref object or callback, which are cleared on unmount.
+ * The ref is read via a function if there are dependencies.
+ */
+export function ref<T extends Element>(
+    el: T,
+    value: Ref<T> | (() => Ref<T> | null | undefined) | null | undefined,
+    deps: Live<unknown>[] | null,
+    signal: AbortSignal
+) {
+    let current: Ref<T> | null | undefined = null;
+    const update = (next: Ref<T> | null | undefined) => {
+        assignRef(current, null);
+        assignRef(next, el);
+        current = next;
+    };
+    if (deps) {
+        const read = value as () => Ref<T> | null | undefined;
+        listenDeps(deps, () => update(read()), signal);
+        update(read());
+    } else {
+        update(value as Ref<T> | null | undefined);
+    }
+    signal.addEventListener("abort", () => update(null));
+}
+
+export type ActionReturn<P> = {
+    update?: (params: P) => void;
+    destroy?: () => void;
+};
+
+export type Action<P = any, T extends Element = Element> = (
+    node: T,
+    params: P
+) => ActionReturn<P> | void;
+
+/**
+ * Calls the action with the element, updates it on every change of the dependencies
+ * and destroys on unmount. The params are read via a function if there are dependencies.
+ */
+export function action<T extends Element, P>(
+    el: T,
+    fn: Action<P, T>,
+    params: P | (() => P),
+    deps: Live<unknown>[] | null,
+    signal: AbortSignal
+) {
+    const read = deps ? (params as () => P) : () => params as P;
+    const result = fn(el, read()) as ActionReturn<P> | undefined;
+    if (deps && result?.update) {
+        listenDeps(deps, () => result.update?.(read()), signal);
+    }
+    if (result?.destroy) {
+        unmountOn(signal, () => result.destroy?.());
+    }
+}
+
+function fragmentOf(html: string): DocumentFragment {
+    const t = document.createElement("template");
+    t.innerHTML = html;
+    return t.content;
+}
+
 export function el(
     html: string,
     fn?: (container: Node, unmountSignal: AbortSignal) => void
 ): Insertable {
-    const frag = (() => {
-        const t = document.createElement("template");
-        t.innerHTML = html;
-        return t.content;
-    })();
+    const frag = fragmentOf(html);
     return new Insertion((target, anchor) => {
         if (fn) {
             const abortController = new AbortController();
@@ -454,6 +1070,10 @@ export function cmp<I extends Insertable, P>(
 export type View<M extends object = {}> = {
     model: M;
     insertTo(target: Element, anchor?: Node | null): InsertedView;
+    /**
+     * Adopts the server-rendered children of the target.
+     */
+    hydrate(target: Element): InsertedView;
 };
 
 export type InsertedView = {
@@ -468,48 +1088,549 @@ export function view<M extends object>(
     model: M,
     insertable: (model: M, unmountSignal: AbortSignal) => Insertable
 ): View<M> {
-    return {
-        model,
-        insertTo(target, anchor = null) {
-            const abortController = new AbortController();
-            const unmountSignal = abortController.signal;
-            const span = new NodeSpan(target, anchor, "view");
-            span.append(insertable(model, unmountSignal));
-            const unmount = (removing: boolean) => {
+    const mount = (span: NodeSpan, hydrating: boolean): InsertedView => {
+        let abortController = new AbortController();
+        if (!hydrating || !span.adopt(insertable(model, abortController.signal))) {
+            if (hydrating) {
+                warnMismatch(span.firstNode.parentNode);
                 abortController.abort();
-                span.unmount(removing);
-            };
-            return {
-                unmountSignal,
-                querySelector(selectors) {
-                    for (const el of span.elementGenerator()) {
-                        const result = el.matches(selectors) ? el : el.querySelector(selectors);
-                        if (result) {
-                            return result;
-                        }
+                abortController = new AbortController();
+                span.clear();
+            }
+            span.append(insertable(model, abortController.signal));
+        }
+        const unmountSignal = abortController.signal;
+        const unmount = (removing: boolean) => {
+            abortController.abort();
+            span.unmount(removing);
+        };
+        return {
+            unmountSignal,
+            querySelector(selectors) {
+                for (const el of span.elementGenerator()) {
+                    const result = el.matches(selectors) ? el : el.querySelector(selectors);
+                    if (result) {
+                        return result;
                     }
-                    return null;
-                },
-                querySelectorAll(selectors) {
-                    let result: Element[] = [];
-                    for (const el of span.elementGenerator()) {
-                        if (el.matches(selectors)) {
-                            result.push(el);
-                        }
-                        const list = el.querySelectorAll(selectors);
-                        if (list.length > 0) {
-                            result.push(...Array.from(list));
-                        }
+                }
+                return null;
+            },
+            querySelectorAll(selectors) {
+                let result: Element[] = [];
+                for (const el of span.elementGenerator()) {
+                    if (el.matches(selectors)) {
+                        result.push(el);
+                    }
+                    const list = el.querySelectorAll(selectors);
+                    if (list.length > 0) {
+                        result.push(...Array.from(list));
                     }
-                    return result;
-                },
-                remove() {
-                    unmount(true);
-                },
-                unmount(removing = false) {
-                    unmount(removing);
                 }
-            };
+                return result;
+            },
+            remove() {
+                unmount(true);
+            },
+            unmount(removing = false) {
+                unmount(removing);
+            }
+        };
+    };
+    return {
+        model,
+        insertTo(target, anchor = null) {
+            return mount(new NodeSpan(target, anchor, "view"), false);
+        },
+        hydrate(target) {
+            return mount(NodeSpan.around(target, "view"), true);
+        }
+    };
+}
+
+// Custom Elements
+
+export type ElementOptions = {
+    /**
+     * Renders the view into the open shadow root
+     */
+    shadow?: boolean,
+};
+
+/**
+ * `initialValue` is observed as the `initial-value` attribute
+ */
+function attrNameOf(param: string): string {
+    return param.replace(/[A-Z]/g, (c) => "-" + c.toLowerCase());
+}
+
+/**
+ * Defines the view as a custom element, which attributes and properties named
+ * after the view parameters update the corresponding model values.
+ */
+export function defineElement<M extends Record<string, Param<unknown>>>(
+    name: string,
+    create: (...args: any[]) => View<M>,
+    params: string[],
+    options?: ElementOptions,
+) {
+    const attrs = new Map(params.map((p) => [attrNameOf(p), p]));
+    class ViewElement extends HTMLElement {
+
+        private view = create();
+
+        private insertedView?: InsertedView;
+
+        static get observedAttributes() {
+            return Array.from(attrs.keys());
+        }
+
+        get model(): M {
+            return this.view.model;
+        }
+
+        connectedCallback() {
+            if (this.isConnected && !this.insertedView) {
+                const target = options?.shadow
+                    ? (this.shadowRoot ?? this.attachShadow({ mode: "open" }))
+                    : this;
+                this.insertedView = this.view.insertTo(target as Element);
+            }
+        }
+
+        disconnectedCallback() {
+            this.insertedView?.remove();
+            this.insertedView = undefined;
+        }
+
+        attributeChangedCallback(attr: string, _?: string | null, newValue?: string | null) {
+            const param = attrs.get(attr);
+            if (param) {
+                this.view.model[param].setValue(newValue);
+            }
+        }
+    }
+    params.forEach((param) => {
+        Object.defineProperty(ViewElement.prototype, param, {
+            get(this: ViewElement) {
+                return this.model[param].getValue();
+            },
+            set(this: ViewElement, value: unknown) {
+                this.model[param].setValue(value);
+            },
+        });
+    });
+    customElements.define(name, ViewElement);
+}
+
+// Hydration
+
+const MARKER_START = "[";
+const MARKER_END = "]";
+
+function isMarker(node: Node | null, data: string): boolean {
+    return node?.nodeType === Node.COMMENT_NODE && (node as Comment).data === data;
+}
+
+/**
+ * Returns the matching `<!--]-->` marker, if the node is the `<!--[-->` one.
+ */
+function endMarkerOf(node: Node): Node | null {
+    let depth = 0;
+    let current: Node | null = node.nextSibling;
+    while (current) {
+        if (isMarker(current, MARKER_START)) {
+            depth += 1;
+        } else if (isMarker(current, MARKER_END)) {
+            if (depth === 0) {
+                return current;
+            }
+            depth -= 1;
+        }
+        current = current.nextSibling;
+    }
+    return null;
+}
+
+function startMarkerOf(node: Node): Node | null {
+    let depth = 0;
+    let current: Node | null = node.previousSibling;
+    while (current) {
+        if (isMarker(current, MARKER_END)) {
+            depth += 1;
+        } else if (isMarker(current, MARKER_START)) {
+            if (depth === 0) {
+                return current;
+            }
+            depth -= 1;
+        }
+        current = current.previousSibling;
+    }
+    return null;
+}
+
+/**
+ * Returns the node to insert before. If it's the `<!--[-->` marker, the
+ * matching `<!--]-->` one becomes the anchor, so `insert` adopts the
+ * server-rendered content between them.
+ */
+export function anchor(node: Node): Node {
+    if (!isMarker(node, MARKER_START)) {
+        return node;
+    }
+    return endMarkerOf(node) ?? node;
+}
+
+function warnMismatch(target: Node | null) {
+    console.warn("viewmill: the server-rendered nodes don't match, so they're rendered again", target);
+}
+
+function isWhitespace(node: Node | null): boolean {
+    return node?.nodeType === Node.TEXT_NODE && !/\S/.test((node as Text).data);
+}
+
+/**
+ * Whitespace text nodes may appear around the server-rendered elements,
+ * e.g. if the markup is formatted, so they're skipped looking for one.
+ */
+function skipWhitespace(node: Node | null): Node | null {
+    while (isWhitespace(node)) {
+        node = node!.nextSibling;
+    }
+    return node;
+}
+
+/**
+ * Checks if the node has the same type and tag name as the template, and
+ * so do its children, where `<!>` stands for the markers. The children of
+ * an empty template aren't checked, as they may come from `innerHTML` or
+ * `textContent`.
+ */
+function matchesTemplate(template: Node, node: Node | null): boolean {
+    if (!node || node.nodeType !== template.nodeType) {
+        return false;
+    }
+    if (template.nodeType !== Node.ELEMENT_NODE) {
+        return true;
+    }
+    if ((node as Element).tagName !== (template as Element).tagName) {
+        return false;
+    }
+    if (!template.firstChild) {
+        return true;
+    }
+    let current: Node | null = node.firstChild;
+    for (let child = template.firstChild; child; child = child.nextSibling) {
+        if (isMarker(child, "")) {
+            current = isMarker(current, MARKER_START) ? endMarkerOf(current!) : null;
+            if (!current) {
+                return false;
+            }
+        } else if (!matchesTemplate(child, current)) {
+            return false;
         }
+        current = current!.nextSibling;
+    }
+    return current === null;
+}
+
+/**
+ * Adopts the nodes between the markers, or removes them and inserts the
+ * input again, if they don't match.
+ */
+function hydrateRange(
+    input: Insertable,
+    target: Node,
+    start: Node,
+    end: Node
+): Unmounter | null {
+    const adopted = adoptNodes(input, target, start.nextSibling);
+    if (adopted && adopted[1] === end) {
+        target.removeChild(start);
+        return adopted[0];
+    }
+    adopted?.[0]?.(false);
+    warnMismatch(target);
+    const range = document.createRange();
+    range.setStartBefore(start);
+    range.setEndBefore(end);
+    range.deleteContents();
+    return insertNodes(input, target, end);
+}
+
+export type Adopted = [Unmounter | null, Node | null];
+
+/**
+ * An insertion, which is also able to adopt the existing nodes from the
+ * cursor. It returns the next node to adopt, or null if they don't match.
+ */
+export class Adoption extends Insertion {
+    constructor(
+        insertTo: (target: Node, anchor: Node | null) => Unmounter | null,
+        public adopt: (target: Node, cursor: Node | null) => Adopted | null
+    ) {
+        super(insertTo);
+    }
+}
+
+/**
+ * The same as `el`, but the function gets the element itself, so it's
+ * possible to adopt a server-rendered one.
+ */
+export function adopt(
+    html: string,
+    fn?: (node: Node, unmountSignal: AbortSignal) => void
+): Insertable {
+    const frag = fragmentOf(html);
+    const template = frag.firstChild!;
+    const mount = (node: Node): Unmounter => {
+        const abortController = new AbortController();
+        fn?.(node, abortController.signal);
+        return (removing) => {
+            abortController.abort();
+            if (removing) {
+                node.parentNode?.removeChild(node);
+            }
+        };
     };
+    return new Adoption(
+        (target, anchor) => {
+            const node = template.cloneNode(true);
+            const un = mount(node);
+            target.insertBefore(node, anchor);
+            return un;
+        },
+        (_, cursor) => {
+            const node = skipWhitespace(cursor);
+            if (!node || !matchesTemplate(template, node)) {
+                return null;
+            }
+            const next = node.nextSibling;
+            return [mount(node), next];
+        }
+    );
+}
+
+/**
+ * Adopts a text node starting with the data, which is split off, as the
+ * server merges adjacent strings.
+ */
+function adoptText(
+    data: string,
+    target: Node,
+    cursor: Node | null
+): [Text, Node | null] | null {
+    if (data === "") {
+        return [target.insertBefore(document.createTextNode(""), cursor), cursor];
+    }
+    if (cursor?.nodeType !== Node.TEXT_NODE || !(cursor as Text).data.startsWith(data)) {
+        return null;
+    }
+    const txt = cursor as Text;
+    if (txt.data.length > data.length) {
+        txt.splitText(data.length);
+    }
+    return [txt, txt.nextSibling];
+}
+
+function adoptNodes(
+    input: Insertable,
+    target: Node,
+    cursor: Node | null
+): Adopted | null {
+    if (input === null || typeof input === "undefined") {
+        return [null, cursor];
+    } else if (
+        typeof input === "object" &&
+        !(input instanceof Insertion) &&
+        !(input instanceof Node) &&
+        typeof input[Symbol.iterator] === "function"
+    ) {
+        const list: (Unmounter | null)[] = [];
+        const unmount: Unmounter = (removing) => list.forEach((u) => u?.(removing));
+        for (const entry of input) {
+            const adopted = adoptNodes(entry, target, cursor);
+            if (!adopted) {
+                unmount(false);
+                return null;
+            }
+            list.push(adopted[0]);
+            cursor = adopted[1];
+        }
+        return [unmount, cursor];
+    }
+    const node = skipWhitespace(cursor);
+    const end = node && isMarker(node, MARKER_START) ? endMarkerOf(node) : null;
+    if (node && end) {
+        // Everything, except elements, is rendered between the markers
+        const un = hydrateRange(input, target, node, end);
+        return [
+            (removing) => {
+                un?.(removing);
+                if (removing) {
+                    target.removeChild(end);
+                }
+            },
+            end.nextSibling
+        ];
+    } else if (input instanceof Adoption) {
+        return input.adopt(target, cursor);
+    } else if (typeof input === "object") {
+        // Nodes and other insertions aren't rendered by the server
+        return null;
+    } else {
+        const adopted = adoptText(String(input), target, cursor);
+        return adopted && [
+            (removing) => {
+                if (removing) {
+                    target.removeChild(adopted[0]);
+                }
+            },
+            adopted[1]
+        ];
+    }
+}
+
+// Server-side rendering
+
+const HTML_ESCAPES: Record<string, string> = {
+    "&": "&amp;",
+    "<": "&lt;",
+    ">": "&gt;",
+    "\"": "&quot;",
+    "'": "&#39;"
+};
+
+export function escapeHtml(s: string): string {
+    return s.replace(/[&<>"']/g, (c) => HTML_ESCAPES[c]);
+}
+
+/**
+ * A ready-to-use markup, which isn't escaped while rendering.
+ */
+export class Html {
+    constructor(public readonly value: string) { }
+
+    toString() {
+        return this.value;
+    }
+}
+
+export function html(value: unknown): Html {
+    return new Html(
+        value === null || typeof value === "undefined" ? "" : String(value)
+    );
+}
+
+export class ServerView<M extends object = {}> {
+    constructor(
+        public readonly model: M,
+        private render: (model: M) => unknown
+    ) { }
+
+    renderToString(): string {
+        return rootToHtml(this.render(this.model));
+    }
+}
+
+export function serverView<M extends object>(
+    model: M,
+    render: (model: M) => unknown
+): ServerView<M> {
+    return new ServerView(model, render);
+}
+
+/**
+ * Renders the same way as `insert` does, escaping everything except markup.
+ */
+export function toHtml(input: unknown): string {
+    if (input === null || typeof input === "undefined") {
+        return "";
+    } else if (input instanceof Html) {
+        return input.value;
+    } else if (input instanceof ServerView) {
+        return input.renderToString();
+    } else if (typeof input === "object" && typeof (input as Iterable<unknown>)[Symbol.iterator] === "function") {
+        let result = "";
+        for (const entry of input as Iterable<unknown>) {
+            result += toHtml(entry);
+        }
+        return result;
+    } else {
+        return escapeHtml(String(input));
+    }
+}
+
+/**
+ * Wraps everything except elements with markers, so hydration knows
+ * which nodes to adopt.
+ */
+function rootToHtml(input: unknown): string {
+    if (input === null || typeof input === "undefined") {
+        return "";
+    } else if (input instanceof Html) {
+        return input.value;
+    } else if (typeof input === "object" && typeof (input as Iterable<unknown>)[Symbol.iterator] === "function") {
+        let result = "";
+        for (const entry of input as Iterable<unknown>) {
+            result += rootToHtml(entry);
+        }
+        return result;
+    } else {
+        return `<!--${MARKER_START}-->${toHtml(input)}<!--${MARKER_END}-->`;
+    }
+}
+
+export function attrToHtml(name: string, value: unknown): string {
+    if (value === null || typeof value === "undefined" || value === false) {
+        return "";
+    } else if (value === true) {
+        return " " + name;
+    } else {
+        return ` ${name}="${escapeHtml(String(value))}"`;
+    }
+}
+
+export function attrsToHtml(values: Record<string, unknown>): string {
+    return Object.keys(values)
+        .map((key) => attrToHtml(key, values[key]))
+        .join("");
+}
+
+/**
+ * Renders the `class` attribute from `[token, on]` pairs in order, so later
+ * tokens are added or removed like the element's `classList` would do.
+ */
+export function classToHtml(tokens: [unknown, unknown][]): string {
+    const result = new Set<string>();
+    tokens.forEach(([token, on]) => {
+        classTokens(token).forEach((t) => {
+            if (on) {
+                result.add(t);
+            } else {
+                result.delete(t);
+            }
+        });
+    });
+    return result.size > 0 ? attrToHtml("class", Array.from(result).join(" ")) : "";
+}
+
+/**
+ * Renders the `style` attribute from `[name, value]` pairs in order, where
+ * the `null` name means the value consists of raw declarations.
+ */
+export function styleToHtml(entries: [string | null, unknown][]): string {
+    const result = new Map<string, string>();
+    entries.forEach(([name, value]) => {
+        if (name !== null) {
+            if (value === null || typeof value === "undefined" || value === false) {
+                result.delete(name);
+            } else {
+                result.set(name, String(value));
+            }
+        } else {
+            styleDeclarations(value).forEach(([name, value]) => result.set(name, value));
+        }
+    });
+    const decls = Array.from(result).map(([name, value]) => `${name}: ${value}`);
+    return decls.length > 0 ? attrToHtml("style", decls.join("; ")) : "";
 }
@@ -58,6 +58,23 @@ const cases = [
         assertEq(v, 11);
     },
 
+    () => {
+        let i = 0;
+        const form = document.createElement("form");
+        const btn = document.createElement("button");
+        form.appendChild(btn);
+        let bubbled = 0;
+        form.addEventListener("click", () => (bubbled += 1));
+        viewmill.listen(btn, "click", (e) => {
+            assertEq(e.defaultPrevented, true);
+            i += 1;
+        }, null, null, { prevent: true, stop: true, once: true });
+        btn.click();
+        btn.click();
+        assertEq(i, 1);
+        assertEq(bubbled, 1);
+    },
+
     //
     // live
     //
@@ -106,6 +123,101 @@ const cases = [
         assertEq(baz.getValue(), 22);
     },
 
+    () => {
+        const a = viewmill.param(1);
+        const b = viewmill.param(2);
+        const pair = viewmill.live(
+            () => [a.getValue(), b.getValue()],
+            [a, b],
+            null,
+            undefined,
+            viewmill.shallowEqual
+        );
+        let changes = 0;
+        pair.listen(() => (changes += 1));
+        a.setValue(1);
+        b.updateValue((v) => v);
+        assertEq(changes, 0);
+        a.setValue(11);
+        assertEq(changes, 1);
+        assertEq(pair.getValue()[0], 11);
+        const c = viewmill.param({ x: 1, y: [1] });
+        const [x, y] = viewmill.live(
+            () => c.getValue(),
+            [c],
+            [2, ({ x, y }) => [x, y]],
+            undefined,
+            viewmill.shallowEqual
+        );
+        let ys = 0;
+        y.listen(() => (ys += 1));
+        c.setValue({ x: 2, y: [1] });
+        assertEq(x.getValue(), 2);
+        assertEq(ys, 0);
+    },
+
+    () => {
+        assertEq(viewmill.shallowEqual([1, "a"], [1, "a"]), true);
+        assertEq(viewmill.shallowEqual([1, "a"], [1, "b"]), false);
+        assertEq(viewmill.shallowEqual([1], [1, 2]), false);
+        assertEq(viewmill.shallowEqual({ a: 1, b: 2 }, { b: 2, a: 1 }), true);
+        assertEq(viewmill.shallowEqual({ a: 1 }, { a: 1, b: undefined }), false);
+        assertEq(viewmill.shallowEqual({ a: [] }, { a: [] }), false);
+        assertEq(viewmill.shallowEqual([], {}), false);
+        assertEq(viewmill.shallowEqual(new Date(0), new Date(0)), false);
+    },
+
+    //
+    // effect
+    //
+
+    () => {
+        const abortController = new AbortController();
+        const a = viewmill.param(1);
+        const out: number[] = [];
+        viewmill.effect(() => out.push(a.getValue()), [a], abortController.signal);
+        assertEq(out.join(), "1");
+        a.setValue(2);
+        assertEq(out.join(), "1,2");
+        abortController.abort();
+        a.setValue(3);
+        assertEq(out.join(), "1,2");
+        viewmill.effect(() => out.push(0), [a], abortController.signal);
+        assertEq(out.join(), "1,2");
+    },
+
+    //
+    // member
+    //
+
+    () => {
+        const user = viewmill.param<{ name: string, age: number, address?: { city: string } }>({
+            name: "foo",
+            age: 1
+        });
+        const name = viewmill.member<string>(user, "name");
+        const city = viewmill.member<string>(user, "address", "city");
+        assertEq(name.getValue(), "foo");
+        assertEq(city.getValue(), undefined);
+        const names: viewmill.ChangeEventValue<string>[] = [];
+        name.listen((v) => names.push(v));
+        let cities = 0;
+        city.listen(() => (cities += 1));
+        user.setValue({ name: "foo", age: 2 });
+        assertEq(names.length, 0);
+        assertEq(cities, 0);
+        user.setValue({ name: "bar", age: 2, address: { city: "baz" } });
+        assertEq(names.length, 1);
+        assertEq(names[0].oldValue, "foo");
+        assertEq(names[0].newValue, "bar");
+        assertEq(city.getValue(), "baz");
+        assertEq(cities, 1);
+        const greeting = viewmill.live(() => `Hi, ${name.getValue()}`, [name]);
+        user.setValue({ name: "bar", age: 3 });
+        assertEq(greeting.getValue(), "Hi, bar");
+        assertEq(cities, 2);
+    },
+
     //
     // param
     //
@@ -130,6 +242,25 @@ const cases = [
         assertEq(last.userData, 1024);
     },
 
+    //
+    // state
+    //
+
+    () => {
+        const [a, setA] = viewmill.state(1);
+        assertEq(a, 1);
+        setA(2);
+        assertEq(a, 1);
+        const [b, setB] = viewmill.localState(1);
+        let changes = 0;
+        b.listen(() => (changes += 1));
+        setB(1);
+        assertEq(changes, 0);
+        setB(2);
+        assertEq(b.getValue(), 2);
+        assertEq(changes, 1);
+    },
+
     //
     // insert
     //
@@ -345,6 +476,79 @@ const cases = [
         assertEq(target.lastChild, null);
     },
 
+    //
+    // keyed
+    //
+
+    () => {
+        const target = document.createElement("div");
+        const [ia, ib, ic] = [
+            { id: 1, text: "a" },
+            { id: 2, text: "b" },
+            { id: 3, text: "c" }
+        ];
+        const a = viewmill.param([ia, ib, ic]);
+        let renderCount = 0;
+        const unmount = viewmill.insert(
+            viewmill.keyed(
+                () => a.getValue(),
+                (item) => item.id,
+                (item) => {
+                    renderCount += 1;
+                    const el = document.createElement("p");
+                    el.textContent = item.text;
+                    return el;
+                },
+                [a]
+            ),
+            target,
+            null
+        )!;
+        assertEq(noComments(target.innerHTML), "<p>a</p><p>b</p><p>c</p>");
+        assertEq(renderCount, 3);
+        const [pa, , pc] = Array.from(target.querySelectorAll("p"));
+        a.setValue([ic, { id: 4, text: "d" }, ia]);
+        assertEq(noComments(target.innerHTML), "<p>c</p><p>d</p><p>a</p>");
+        assertEq(renderCount, 4);
+        const list = target.querySelectorAll("p");
+        assertEq(list[0], pc);
+        assertEq(list[2], pa);
+        a.setValue([]);
+        assertEq(noComments(target.innerHTML), "");
+        unmount(true);
+        assertEq(target.lastChild, null);
+    },
+
+    () => {
+        const target = document.createElement("div");
+        const [ia, ib] = [{ id: 1, text: "a" }, { id: 2, text: "b" }];
+        const a = viewmill.param([ia, ib]);
+        let renderCount = 0;
+        const unmount = viewmill.insert(
+            viewmill.keyed(
+                () => a.getValue(),
+                (item) => item.id,
+                (item) => {
+                    renderCount += 1;
+                    const el = document.createElement("p");
+                    el.textContent = item.text;
+                    return el;
+                },
+                [a]
+            ),
+            target,
+            null
+        )!;
+        const pb = target.querySelectorAll("p")[1];
+        // The item is replaced under the same key
+        a.setValue([{ id: 1, text: "A" }, ib]);
+        assertEq(noComments(target.innerHTML), "<p>A</p><p>b</p>");
+        assertEq(renderCount, 3);
+        assertEq(target.querySelectorAll("p")[1], pb);
+        unmount(true);
+        assertEq(target.lastChild, null);
+    },
+
     //
     // cond
     //
@@ -425,6 +629,33 @@ const cases = [
         assertEq(target.lastChild?.textContent, "text");
     },
 
+    //
+    // text
+    //
+
+    () => {
+        const target = document.createElement("div");
+
+        const a = viewmill.param<number | null>(1);
+        const unmount = viewmill.insert(
+            viewmill.text(() => a.getValue(), [a]),
+            target
+        )!;
+        target.appendChild(document.createTextNode("text"));
+        const node = target.firstChild;
+        assertEq(node?.textContent, "1");
+        a.setValue(2);
+        assertEq(target.firstChild, node);
+        assertEq(node?.textContent, "2");
+        a.setValue(null);
+        assertEq(node?.textContent, "");
+        unmount(true);
+        assertEq(target.childNodes.length, 1);
+        assertEq(target.lastChild?.textContent, "text");
+        a.setValue(3);
+        assertEq(node?.textContent, "");
+    },
+
     //
     // attrs
     //
@@ -490,6 +721,139 @@ const cases = [
         assertEq(el.getAttribute("bar"), null);
     },
 
+    //
+    // prop
+    //
+
+    () => {
+        const el = document.createElement("input");
+        el.type = "checkbox";
+        viewmill.prop(el, "checked", true);
+        assertEq(el.checked, true);
+        assertEq(el.getAttribute("checked"), null);
+
+        const checked = viewmill.param(false);
+        viewmill.prop(el, "checked", () => checked.getValue(), [checked]);
+        assertEq(el.checked, false);
+        checked.setValue(true);
+        assertEq(el.checked, true);
+        el.click();
+        assertEq(el.checked, false);
+        checked.setValue(false);
+        checked.setValue(true);
+        assertEq(el.checked, true);
+    },
+
+    //
+    // class & style
+    //
+
+    () => {
+        const el = document.createElement("div");
+        el.className = "box";
+        const active = viewmill.param(true);
+        viewmill.toggleClass(el, "active", () => active.getValue(), [active]);
+        assertEq(el.className, "box active");
+        el.classList.add("external");
+        active.setValue(false);
+        assertEq(el.className, "box external");
+        viewmill.toggleClass(el, "static", true);
+        assertEq(el.classList.contains("static"), true);
+    },
+
+    () => {
+        const el = document.createElement("div");
+        el.setAttribute("style", "margin: 0px");
+        const color = viewmill.param<string | null>("red");
+        viewmill.style(el, "color", () => color.getValue(), [color]);
+        viewmill.style(el, "--accent", "blue");
+        assertEq(el.style.color, "red");
+        assertEq(el.style.margin, "0px");
+        assertEq(el.style.getPropertyValue("--accent"), "blue");
+        color.setValue(null);
+        assertEq(el.style.color, "");
+        assertEq(el.style.margin, "0px");
+    },
+
+    () => {
+        const el = document.createElement("div");
+        const cls = viewmill.param<string | null>("a b");
+        const active = viewmill.param(true);
+        viewmill.mergeClass(el, () => cls.getValue(), [cls]);
+        viewmill.toggleClass(el, "active", () => active.getValue(), [active]);
+        assertEq(el.className, "a b active");
+        cls.setValue("b c");
+        assertEq(el.className, "b active c");
+        cls.setValue(null);
+        assertEq(el.className, "active");
+    },
+
+    () => {
+        const el = document.createElement("div");
+        const decls = viewmill.param("margin: 0px; padding: 1px");
+        const color = viewmill.param("red");
+        viewmill.mergeStyle(el, () => decls.getValue(), [decls]);
+        viewmill.style(el, "color", () => color.getValue(), [color]);
+        assertEq(el.style.margin, "0px");
+        assertEq(el.style.color, "red");
+        decls.setValue("padding: 2px");
+        assertEq(el.style.margin, "");
+        assertEq(el.style.padding, "2px");
+        assertEq(el.style.color, "red");
+    },
+
+    //
+    // ref
+    //
+
+    () => {
+        const el = document.createElement("input");
+        const abortController = new AbortController();
+        const obj = viewmill.createRef<HTMLInputElement>();
+        viewmill.ref(el, obj, null, abortController.signal);
+        assertEq(obj.current, el);
+        const nodes: (Element | null)[] = [];
+        const a = viewmill.param<viewmill.Ref<HTMLInputElement>>((node) => nodes.push(node));
+        viewmill.ref(el, () => a.getValue(), [a], abortController.signal);
+        assertArrayEq(nodes, [el]);
+        const other = viewmill.createRef<HTMLInputElement>();
+        a.setValue(other);
+        assertArrayEq(nodes, [el, null]);
+        assertEq(other.current, el);
+        abortController.abort();
+        assertEq(obj.current, null);
+        assertEq(other.current, null);
+        assertArrayEq(nodes, [el, null]);
+    },
+
+    //
+    // action
+    //
+
+    () => {
+        const el = document.createElement("div");
+        const abortController = new AbortController();
+        const calls: string[] = [];
+        const text = viewmill.param("a");
+        viewmill.action(el, (node: Element, params: { text: string }) => {
+            calls.push(`init ${params.text}`);
+            assertEq(node, el);
+            return {
+                update: (params) => calls.push(`update ${params.text}`),
+                destroy: () => calls.push("destroy")
+            };
+        }, () => ({ text: text.getValue() }), [text], abortController.signal);
+        viewmill.action(el, () => {
+            calls.push("plain");
+        }, undefined, null, abortController.signal);
+        assertArrayEq(calls, ["init a", "plain"]);
+        text.setValue("b");
+        assertArrayEq(calls, ["init a", "plain", "update b"]);
+        abortController.abort();
+        text.setValue("c");
+        assertArrayEq(calls, ["init a", "plain", "update b", "destroy"]);
+    },
+
     //
     // el
     //
@@ -912,6 +1276,166 @@ const cases = [
         assertEq(unmounted, false);
         unmount();
         assertEq(unmounted, true);
+    },
+
+    //
+    // ssr
+    //
+
+    () => {
+        assertEq(viewmill.toHtml(null), "");
+        assertEq(viewmill.toHtml("<a> & \"b\""), "&lt;a&gt; &amp; &quot;b&quot;");
+        assertEq(viewmill.toHtml([1, false, viewmill.html("<br>")]), "1false<br>");
+        assertEq(viewmill.attrToHtml("title", "\"x\""), ' title="&quot;x&quot;"');
+        assertEq(viewmill.attrToHtml("disabled", true), " disabled");
+        assertEq(viewmill.attrToHtml("disabled", false), "");
+        assertEq(viewmill.attrsToHtml({ a: "1", b: true, c: null }), ' a="1" b');
+        assertEq(viewmill.classToHtml([["a b", true], ["c", 1], ["a", false], [null, true]]), ' class="b c"');
+        assertEq(viewmill.classToHtml([["a", false]]), "");
+        assertEq(
+            viewmill.styleToHtml([[null, "margin: 0; color: red"], ["color", "blue"], ["margin", null]]),
+            ' style="color: blue"'
+        );
+        assertEq(viewmill.styleToHtml([["color", false]]), "");
+        const v = viewmill.serverView({ name: "<b>" }, ({ name }) => [
+            viewmill.html("<p>" + viewmill.toHtml(name) + "</p>"),
+            viewmill.serverView({}, () => "!")
+        ]);
+        assertEq(v.renderToString(), "<p>&lt;b&gt;</p><!--[-->!<!--]-->");
+    },
+
+    //
+    // hydration
+    //
+
+    () => {
+        const target = document.createElement("div");
+        target.innerHTML = "<p>a<!--[-->old<!--[-->nested<!--]--><!--]-->b</p>";
+        const p = target.firstChild!;
+        const end = viewmill.anchor(p.firstChild!.nextSibling!);
+        assertEq(end.nodeType, Node.COMMENT_NODE);
+        assertEq(end.previousSibling?.nodeType, Node.COMMENT_NODE);
+        assertEq(end.nextSibling?.textContent, "b");
+        assertEq(p.textContent, "aoldnestedb");
+        const text = p.firstChild!;
+        assertEq(viewmill.anchor(text), text);
+    },
+
+    () => {
+        const a = viewmill.param(1);
+        const clicks: number[] = [];
+        const v = viewmill.view({ a }, ({ a }, unmountSignal) => [
+            viewmill.adopt("<p><!></p>", (node, unmountSignal1) => {
+                const anchor = viewmill.anchor(node.firstChild!);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(
+                    viewmill.text(() => a.getValue(), [a]),
+                    node,
+                    anchor
+                ));
+                viewmill.listen(node, "click", () => clicks.push(a.getValue()));
+            }),
+            "text"
+        ]);
+        const target = document.createElement("div");
+        target.innerHTML = "<p><!--[-->1<!--]--></p><!--[-->text<!--]-->";
+        const p = target.querySelector("p")!;
+        const inserted = v.hydrate(target);
+        assertEq(target.querySelector("p"), p);
+        assertEq(p.textContent, "1");
+        a.setValue(2);
+        assertEq(p.textContent, "2");
+        p.click();
+        assertArrayEq(clicks, [2]);
+        assertEq(noComments(target.innerHTML), "<p>2</p>text");
+        inserted.remove();
+        assertEq(target.childNodes.length, 0);
+
+        const fresh = document.createElement("div");
+        v.insertTo(fresh);
+        assertEq(noComments(fresh.innerHTML), "<p>2</p>text");
+    },
+
+    () => {
+        const items = viewmill.param([{ id: 1, name: "a" }, { id: 2, name: "b" }]);
+        const v = viewmill.view({ items }, ({ items }) => [
+            viewmill.adopt("<ul><!></ul>", (node, unmountSignal1) => {
+                const anchor = viewmill.anchor(node.firstChild!);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.keyed(
+                    () => items.getValue(),
+                    (item) => item.id,
+                    (item) => viewmill.adopt("<li><!></li>", (node1, unmountSignal2) => {
+                        const anchor1 = viewmill.anchor(node1.firstChild!);
+                        viewmill.unmountOn(unmountSignal2, viewmill.insert(item.name, node1, anchor1));
+                    }),
+                    [items]
+                ), node, anchor));
+            })
+        ]);
+        const target = document.createElement("div");
+        target.innerHTML = "\n  <ul><!--[--><li><!--[-->a<!--]--></li><li><!--[-->b<!--]--></li><!--]--></ul>\n";
+        const ul = target.querySelector("ul")!;
+        const [a, b] = Array.from(ul.querySelectorAll("li"));
+        v.hydrate(target);
+        assertEq(target.querySelector("ul"), ul);
+        assertArrayEq(Array.from(ul.querySelectorAll("li")), [a, b]);
+        items.setValue([items.getValue()[1], items.getValue()[0]]);
+        assertArrayEq(Array.from(ul.querySelectorAll("li")), [b, a]);
+        assertEq(noComments(ul.innerHTML), "<li>b</li><li>a</li>");
+    },
+
+    () => {
+        const warn = console.warn;
+        let warnings = 0;
+        console.warn = () => (warnings += 1);
+        const v = viewmill.view({}, () => [
+            viewmill.adopt("<p><!></p>", (node, unmountSignal1) => {
+                const anchor = viewmill.anchor(node.firstChild!);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert("a", node, anchor));
+            })
+        ]);
+        const target = document.createElement("div");
+        target.innerHTML = "<div>a</div>";
+        v.hydrate(target);
+        assertEq(warnings, 1);
+        assertEq(noComments(target.innerHTML), "<p>a</p>");
+
+        const p = document.createElement("div");
+        p.innerHTML = "<p><!--[--><b>a</b><!--]--></p>";
+        v.hydrate(p);
+        assertEq(warnings, 2);
+        assertEq(noComments(p.innerHTML), "<p>a</p>");
+        console.warn = warn;
+    },
+
+    //
+    // defineElement
+    //
+
+    () => {
+        viewmill.defineElement("test-counter", (count?: unknown, maxValue?: unknown) => (
+            viewmill.view({
+                count: viewmill.param(count),
+                maxValue: viewmill.param(maxValue)
+            }, ({ count, maxValue }) => [
+                viewmill.text(() => `${count.getValue()}/${maxValue.getValue()}`, [count, maxValue])
+            ])
+        ), ["count", "maxValue"], { shadow: true });
+        const el = document.createElement("test-counter") as any;
+        el.setAttribute("count", "1");
+        el.setAttribute("max-value", "10");
+        document.body.appendChild(el);
+        assertEq(el.shadowRoot.textContent, "1/10");
+        el.count = 2;
+        assertEq(el.shadowRoot.textContent, "2/10");
+        assertEq(el.model.count.getValue(), 2);
+        el.setAttribute("max-value", "5");
+        assertEq(el.shadowRoot.textContent, "2/5");
+        el.remove();
+        assertEq(el.shadowRoot.textContent, "");
+        // Moving doesn't duplicate the content
+        document.body.appendChild(el);
+        assertEq(el.shadowRoot.textContent, "2/5");
+        el.remove();
     }
 ];
 
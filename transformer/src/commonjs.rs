@@ -0,0 +1,298 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{ast::*, atoms::JsWord},
+};
+
+use super::{glob, utils::*};
+
+/// Lowers imports and exports of the JS output to CommonJS for the `require`
+/// import style, so the file could be loaded by Node as is. Typescript keeps
+/// them, because `tsc` does the same according to its `module` option.
+pub fn to_commonjs(module: &mut Module, src: &str) {
+    let mut has_exports = false;
+    let mut body = Vec::with_capacity(module.body.len());
+    for item in module.body.drain(..) {
+        let decl = match item {
+            ModuleItem::ModuleDecl(decl) => decl,
+            ModuleItem::Stmt(stmt) => {
+                body.push(stmt);
+                continue;
+            }
+        };
+        has_exports |= !matches!(decl, ModuleDecl::Import(_));
+        match decl {
+            ModuleDecl::Import(decl) => body.extend(import_stmts(decl, src)),
+            ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                let names = decl_names(&decl);
+                body.push(Stmt::Decl(decl));
+                body.extend(
+                    names
+                        .iter()
+                        .map(|name| export_getter_stmt(name, ident_expr(name))),
+                );
+            }
+            ModuleDecl::ExportNamed(export) => {
+                let src = export.src.map(|src| require_expr(*src));
+                for s in export.specifiers {
+                    let (name, value) = match s {
+                        ExportSpecifier::Named(s) => {
+                            let orig = export_name(&s.orig);
+                            let name = s.exported.as_ref().map(export_name);
+                            let name = name.unwrap_or_else(|| orig.clone());
+                            match &src {
+                                Some(src) => (name, member_expr(src.clone(), member_prop(&orig))),
+                                None => {
+                                    // The local may be declared later or reassigned
+                                    body.push(export_getter_stmt(&name, ident_expr(&orig)));
+                                    continue;
+                                }
+                            }
+                        }
+                        ExportSpecifier::Namespace(s) => match &src {
+                            Some(src) => (export_name(&s.name), src.clone()),
+                            None => continue,
+                        },
+                        ExportSpecifier::Default(s) => match &src {
+                            Some(src) => (
+                                s.exported.sym.clone(),
+                                member_expr(src.clone(), member_prop(&"default".into())),
+                            ),
+                            None => continue,
+                        },
+                    };
+                    body.push(export_stmt(&name, value));
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(export) => {
+                let value: Box<Expr> = match export.decl {
+                    DefaultDecl::Fn(FnExpr {
+                        ident: Some(ident),
+                        function,
+                    }) => {
+                        let name = ident.sym.clone();
+                        body.push(Stmt::Decl(Decl::Fn(FnDecl {
+                            ident,
+                            declare: false,
+                            function,
+                        })));
+                        ident_expr(&name)
+                    }
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }) => {
+                        let name = ident.sym.clone();
+                        body.push(Stmt::Decl(Decl::Class(ClassDecl {
+                            ident,
+                            declare: false,
+                            class,
+                        })));
+                        ident_expr(&name)
+                    }
+                    DefaultDecl::Fn(expr) => Box::from(expr),
+                    DefaultDecl::Class(expr) => Box::from(expr),
+                    DefaultDecl::TsInterfaceDecl(_) => continue,
+                };
+                body.push(export_stmt(&"default".into(), value));
+            }
+            ModuleDecl::ExportDefaultExpr(export) => {
+                body.push(export_stmt(&"default".into(), export.expr));
+            }
+            ModuleDecl::ExportAll(export) => {
+                // `Object.assign(exports, require("..."))`
+                let assign = spanned_obj_method_call(
+                    DUMMY_SP,
+                    ident_expr(&"Object".into()),
+                    &"assign".into(),
+                    Some(ArgsBuilder::build_using(|args| {
+                        args.add_expr(exports_expr());
+                        args.add_expr(require_expr(*export.src));
+                    })),
+                );
+                body.push(stmt_from_expr(assign));
+            }
+            ModuleDecl::TsImportEquals(_)
+            | ModuleDecl::TsExportAssignment(_)
+            | ModuleDecl::TsNamespaceExport(_) => (),
+        };
+    }
+    if has_exports {
+        // Marks the module for the default import interop of bundlers and `tsc`
+        let mut descriptor = ObjLitBuilder::default();
+        descriptor.add_key(PropName::from(ident(&"value".into())), Box::from(true));
+        body.insert(0, define_export_stmt(&"__esModule".into(), descriptor));
+    }
+    module.body = body.into_iter().map(ModuleItem::Stmt).collect();
+}
+
+/// `import a, { b as c } from "..."` becomes `const a_module = require("...")`, then the
+/// default export or the module itself if it isn't an ES one, and `const { b: c } = a_module`
+fn import_stmts(decl: ImportDecl, src: &str) -> Vec<Stmt> {
+    let require = require_expr(*decl.src);
+    if decl.specifiers.is_empty() {
+        return vec![stmt_from_expr(require)];
+    }
+    let mut stmts = vec![];
+    let mut source = require;
+    let mut default = None;
+    let mut props = vec![];
+    for s in decl.specifiers {
+        match s {
+            ImportSpecifier::Namespace(s) => {
+                stmts.push(const_decl(&s.local.sym, source));
+                source = ident_expr(&s.local.sym);
+            }
+            ImportSpecifier::Default(s) => default = Some(s.local.sym),
+            ImportSpecifier::Named(s) => {
+                let imported = s.imported.as_ref().map(export_name);
+                props.push((imported.unwrap_or_else(|| s.local.sym.clone()), s.local.sym));
+            }
+        }
+    }
+    if let Some(local) = default {
+        let module_name = match &*source {
+            Expr::Ident(ident) => ident.sym.clone(),
+            _ => {
+                let name = glob::uname(&format!("{local}_module"), src);
+                stmts.push(const_decl(&name, source));
+                name
+            }
+        };
+        stmts.push(const_decl(&local, default_interop_expr(&module_name)));
+        source = ident_expr(&module_name);
+    }
+    if !props.is_empty() {
+        let pat = Pat::Object(ObjectPat {
+            span: DUMMY_SP,
+            props: props
+                .into_iter()
+                .map(|(key, local)| {
+                    ObjectPatProp::KeyValue(KeyValuePatProp {
+                        key: match member_prop(&key) {
+                            MemberProp::Ident(key) => PropName::Ident(key),
+                            _ => PropName::Str(Str::from(key)),
+                        },
+                        value: Box::from(ident_pat(&local)),
+                    })
+                })
+                .collect(),
+            optional: false,
+            type_ann: None,
+        });
+        stmts.push(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: pat,
+                init: Some(source),
+                definite: false,
+            }],
+        }))));
+    }
+    stmts
+}
+
+fn decl_names(decl: &Decl) -> Vec<JsWord> {
+    match decl {
+        Decl::Fn(decl) => vec![decl.ident.sym.clone()],
+        Decl::Class(decl) => vec![decl.ident.sym.clone()],
+        Decl::Var(var) => {
+            let mut names = vec![];
+            for decl in var.decls.iter() {
+                walk_every_pat_idents(&decl.name, |ident| names.push(ident.sym.clone()));
+            }
+            names
+        }
+        _ => vec![],
+    }
+}
+
+fn export_name(name: &ModuleExportName) -> JsWord {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.clone(),
+        ModuleExportName::Str(s) => s.value.clone(),
+    }
+}
+
+fn member_prop(name: &JsWord) -> MemberProp {
+    // Reserved words are valid property names
+    let is_ident_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_ident_name {
+        MemberProp::Ident(ident(name))
+    } else {
+        MemberProp::Computed(ComputedPropName {
+            span: DUMMY_SP,
+            expr: Box::from(Str::from(name.clone())),
+        })
+    }
+}
+
+fn exports_expr() -> Box<Expr> {
+    ident_expr(&"exports".into())
+}
+
+fn require_expr(src: Str) -> Box<Expr> {
+    spanned_fn_call(
+        DUMMY_SP,
+        ident_expr(&"require".into()),
+        Some(ArgsBuilder::from(Box::from(Expr::from(src))).build()),
+    )
+}
+
+/// `m && m.__esModule ? m.default : m`
+fn default_interop_expr(module_name: &JsWord) -> Box<Expr> {
+    let module = || ident_expr(module_name);
+    Box::from(CondExpr {
+        span: DUMMY_SP,
+        test: Box::from(BinExpr {
+            span: DUMMY_SP,
+            op: BinaryOp::LogicalAnd,
+            left: module(),
+            right: member_expr(module(), member_prop(&"__esModule".into())),
+        }),
+        cons: member_expr(module(), member_prop(&"default".into())),
+        alt: module(),
+    })
+}
+
+/// `Object.defineProperty(exports, "name", descriptor)`
+fn define_export_stmt(name: &JsWord, descriptor: ObjLitBuilder) -> Stmt {
+    let define = spanned_obj_method_call(
+        DUMMY_SP,
+        ident_expr(&"Object".into()),
+        &"defineProperty".into(),
+        Some(ArgsBuilder::build_using(|args| {
+            args.add_expr(exports_expr());
+            args.add_str(name);
+            args.add_expr(descriptor.build_expr());
+        })),
+    );
+    stmt_from_expr(define)
+}
+
+/// Exports the value via a getter like `export { name }` does, so it stays live
+/// and the local could be declared after the export
+fn export_getter_stmt(name: &JsWord, value: Box<Expr>) -> Stmt {
+    let mut descriptor = ObjLitBuilder::default();
+    descriptor.add_key(PropName::from(ident(&"enumerable".into())), Box::from(true));
+    descriptor.add_key(
+        PropName::from(ident(&"get".into())),
+        arrow_short_expr(None, value),
+    );
+    define_export_stmt(name, descriptor)
+}
+
+/// `exports.name = value`
+fn export_stmt(name: &JsWord, value: Box<Expr>) -> Stmt {
+    stmt_from_expr(Box::from(AssignExpr {
+        span: DUMMY_SP,
+        op: AssignOp::Assign,
+        left: PatOrExpr::Expr(member_expr(exports_expr(), member_prop(name))),
+        right: value,
+    }))
+}
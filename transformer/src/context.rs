@@ -1,22 +1,47 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
 use swc_core::{
-    common::{sync::Lazy, DUMMY_SP},
+    common::{comments::SingleThreadedComments, sync::Lazy, BytePos, Span, DUMMY_SP},
     ecma::{ast::*, atoms::JsWord},
 };
 
 use super::{
     glob,
-    live::{deps_expr, DestructArg},
+    import::ImportStyle,
+    live::{self, deps_expr, Dep, DestructArg, Equality},
+    mode::OutputMode,
     scope::Scope,
+    syntax::Syntax,
     utils::*,
+    Options,
 };
 
 pub struct TrContext {
     lib_name: JsWord,
     unmount_sig_name: JsWord,
+    runtime: Rc<Runtime>,
+}
+
+/// The runtime module shared by every nested context.
+struct Runtime {
+    module: String,
+    style: ImportStyle,
+    syntax: Syntax,
+    mode: OutputMode,
+    src: String,
+    /// Equality pragmas by the positions they lead
+    equalities: BTreeMap<BytePos, Equality>,
+    /// Helper names mapped to their local names (only for `ImportStyle::Named`)
+    used: RefCell<BTreeMap<JsWord, JsWord>>,
 }
 
 impl TrContext {
-    pub fn new(src: &str, scope: &mut Scope) -> Self {
+    pub fn new(
+        src: &str,
+        comments: &SingleThreadedComments,
+        scope: &mut Scope,
+        options: &Options,
+    ) -> Self {
         const LIB: &str = "viewmill";
         const UNMOUNT_SIG: &str = "unmountSignal";
 
@@ -27,6 +52,15 @@ impl TrContext {
         Self {
             lib_name,
             unmount_sig_name,
+            runtime: Rc::new(Runtime {
+                module: options.runtime.clone(),
+                style: options.import_style,
+                syntax: options.syntax,
+                mode: options.mode,
+                src: src.to_string(),
+                equalities: live::equality_pragmas(comments),
+                used: Default::default(),
+            }),
         }
     }
 
@@ -34,26 +68,107 @@ impl TrContext {
         Self {
             lib_name: self.lib_name.clone(),
             unmount_sig_name: sig_name,
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    pub fn mode(&self) -> OutputMode {
+        self.runtime.mode
+    }
+
+    pub fn src(&self) -> &str {
+        &self.runtime.src
+    }
+
+    /// The equality declared by the pragma leading the position
+    pub fn equality_at(&self, pos: BytePos) -> Option<&Equality> {
+        self.runtime.equalities.get(&pos)
+    }
+
+    /// The helper itself, e.g. to pass it as a callback
+    fn helper(&self, name: &JsWord) -> Box<Expr> {
+        match self.runtime.style {
+            ImportStyle::Namespace | ImportStyle::Require => {
+                member_expr(ident_expr(&self.lib_name), MemberProp::Ident(ident(name)))
+            }
+            ImportStyle::Named => {
+                let local = self
+                    .runtime
+                    .used
+                    .borrow_mut()
+                    .entry(name.clone())
+                    .or_insert_with(|| glob::uname(name, &self.runtime.src))
+                    .clone();
+                ident_expr(&local)
+            }
         }
     }
+
+    fn helper_call(&self, span: Span, name: &JsWord, args: Option<Vec<ExprOrSpread>>) -> Box<Expr> {
+        spanned_fn_call(span, self.helper(name), args)
+    }
 }
 
 impl TrContext {
-    pub fn import_decl(&self) -> ImportDecl {
-        const RUNTIME: &str = "viewmill-runtime";
-
-        let src = Str::from(RUNTIME);
-        let s = ImportSpecifier::Namespace(ImportStarAsSpecifier {
-            span: DUMMY_SP,
-            local: ident(&self.lib_name),
-        });
-        ImportDecl {
+    /// Should be called after the transformation to know the used helpers.
+    pub fn import_item(&self) -> ModuleItem {
+        let runtime = &self.runtime;
+        let src = Box::from(Str::from(runtime.module.as_str()));
+        let specifiers = match runtime.style {
+            ImportStyle::Namespace => vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                span: DUMMY_SP,
+                local: ident(&self.lib_name),
+            })],
+            ImportStyle::Named => runtime
+                .used
+                .borrow()
+                .iter()
+                .map(|(name, local)| {
+                    ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local: ident(local),
+                        imported: if name != local {
+                            Some(ModuleExportName::Ident(ident(name)))
+                        } else {
+                            None
+                        },
+                        is_type_only: false,
+                    })
+                })
+                .collect(),
+            ImportStyle::Require => {
+                return match runtime.syntax {
+                    Syntax::Js => {
+                        static_jsword!(REQUIRE, "require");
+                        let call = spanned_fn_call(
+                            DUMMY_SP,
+                            ident_expr(&REQUIRE),
+                            Some(ArgsBuilder::from(Box::from(Expr::from(*src))).build()),
+                        );
+                        ModuleItem::Stmt(const_decl(&self.lib_name, call))
+                    }
+                    Syntax::Ts => ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(Box::new(
+                        TsImportEqualsDecl {
+                            span: DUMMY_SP,
+                            is_export: false,
+                            is_type_only: false,
+                            id: ident(&self.lib_name),
+                            module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef {
+                                span: DUMMY_SP,
+                                expr: *src,
+                            }),
+                        },
+                    ))),
+                };
+            }
+        };
+        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
             span: DUMMY_SP,
-            specifiers: vec![s],
-            src: Box::from(src),
+            specifiers,
+            src,
             type_only: false,
             asserts: None,
-        }
+        }))
     }
 }
 
@@ -61,33 +176,71 @@ impl TrContext {
     pub fn live(
         &self,
         expr: Box<Expr>,
-        deps: &Vec<JsWord>,
+        deps: &[Dep],
         destruct: Option<&DestructArg>,
+        eq: Option<&Equality>,
     ) -> Box<Expr> {
         static_jsword!(LIVE, "live");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        static_jsword!(SHALLOW_EQUAL, "shallowEqual");
+        self.helper_call(
+            DUMMY_SP,
             &LIVE,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(arrow_short_expr(None, expr));
-                args.add_expr(deps_expr(deps));
+                args.add_expr(deps_expr(self, deps));
                 if let Some(d) = destruct {
                     args.add_expr(d.to_expr());
                 } else {
                     args.add_expr(null_expr());
                 }
                 args.add_expr(ident_expr(&self.unmount_sig_name));
+                match eq {
+                    Some(Equality::Shallow) => _ = args.add_expr(self.helper(&SHALLOW_EQUAL)),
+                    Some(Equality::Custom(name)) => _ = args.add_expr(ident_expr(name)),
+                    None => (),
+                }
+            })),
+        )
+    }
+
+    /// A local state of a view as the live value and its setter
+    pub fn local_state(&self, args: Vec<ExprOrSpread>) -> Box<Expr> {
+        static_jsword!(LOCAL_STATE, "localState");
+        self.helper_call(DUMMY_SP, &LOCAL_STATE, Some(args))
+    }
+
+    /// A side effect re-run on every change of its dependencies
+    pub fn effect(&self, expr: Box<Expr>, deps: &[Dep]) -> Box<Expr> {
+        static_jsword!(EFFECT, "effect");
+        self.helper_call(
+            DUMMY_SP,
+            &EFFECT,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(arrow_short_expr(None, expr));
+                args.add_expr(deps_expr(self, deps));
+                args.add_expr(ident_expr(&self.unmount_sig_name));
+            })),
+        )
+    }
+
+    /// A live member of the live value, e.g. `user.name`
+    pub fn member(&self, name: &JsWord, path: &[JsWord]) -> Box<Expr> {
+        static_jsword!(MEMBER, "member");
+        self.helper_call(
+            DUMMY_SP,
+            &MEMBER,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(name));
+                for p in path {
+                    args.add_str(p);
+                }
             })),
         )
     }
 
     pub fn param(&self, initial: Box<Expr>) -> Box<Expr> {
         static_jsword!(PARAM, "param");
-        obj_method_call(
-            ident_expr(&self.lib_name),
-            &PARAM,
-            Some(ArgsBuilder::from(initial).build()),
-        )
+        self.helper_call(DUMMY_SP, &PARAM, Some(ArgsBuilder::from(initial).build()))
     }
 
     pub fn condition(
@@ -95,42 +248,76 @@ impl TrContext {
         test: Box<Expr>,
         cons: Box<Expr>,
         alt: Box<Expr>,
-        deps: &Vec<JsWord>,
+        deps: &[Dep],
     ) -> Box<Expr> {
         static_jsword!(COND, "cond");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &COND,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(arrow_short_expr(None, test))
                     .add_expr(arrow_short_expr(None, cons))
                     .add_expr(arrow_short_expr(None, alt))
-                    .add_expr(deps_expr(deps));
+                    .add_expr(deps_expr(self, deps));
             })),
         )
     }
 
-    pub fn expression(&self, expr: Box<Expr>, deps: &Vec<JsWord>) -> Box<Expr> {
+    pub fn expression(&self, expr: Box<Expr>, deps: &[Dep]) -> Box<Expr> {
         static_jsword!(EXPR, "expr");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &EXPR,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(arrow_short_expr(None, expr))
-                    .add_expr(deps_expr(deps));
+                    .add_expr(deps_expr(self, deps));
             })),
         )
     }
 
-    pub fn list(&self, expr: Box<Expr>, deps: Option<&Vec<JsWord>>) -> Box<Expr> {
+    pub fn text(&self, expr: Box<Expr>, deps: &[Dep]) -> Box<Expr> {
+        static_jsword!(TEXT, "text");
+        self.helper_call(
+            DUMMY_SP,
+            &TEXT,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(arrow_short_expr(None, expr))
+                    .add_expr(deps_expr(self, deps));
+            })),
+        )
+    }
+
+    pub fn list(&self, expr: Box<Expr>, deps: Option<&Vec<Dep>>) -> Box<Expr> {
         static_jsword!(LIST, "list");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &LIST,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(arrow_short_expr(None, expr));
                 if let Some(deps) = deps {
-                    args.add_expr(deps_expr(deps));
+                    args.add_expr(deps_expr(self, deps));
+                }
+            })),
+        )
+    }
+
+    pub fn keyed(
+        &self,
+        items: Box<Expr>,
+        key: Box<Expr>,
+        render: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+    ) -> Box<Expr> {
+        static_jsword!(KEYED, "keyed");
+        self.helper_call(
+            DUMMY_SP,
+            &KEYED,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(arrow_short_expr(None, items))
+                    .add_expr(key)
+                    .add_expr(render);
+                if let Some(deps) = deps {
+                    args.add_expr(deps_expr(self, deps));
                 }
             })),
         )
@@ -141,19 +328,47 @@ impl TrContext {
         node_name: &JsWord,
         name: &str,
         value: Box<Expr>,
-        deps: Option<&Vec<JsWord>>,
+        deps: Option<&Vec<Dep>>,
         sig: Option<&JsWord>,
     ) -> Box<Expr> {
         static_jsword!(ATTR, "attr");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &ATTR,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(ident_expr(node_name));
-                args.add_str(&name);
+                args.add_str(name);
                 if let Some(deps) = deps {
                     args.add_expr(arrow_short_expr(None, value));
-                    args.add_expr(deps_expr(deps));
+                    args.add_expr(deps_expr(self, deps));
+                    if let Some(sig) = sig {
+                        args.add_expr(ident_expr(sig));
+                    }
+                } else {
+                    args.add_expr(value);
+                }
+            })),
+        )
+    }
+
+    pub fn prop(
+        &self,
+        node_name: &JsWord,
+        name: &str,
+        value: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+        sig: Option<&JsWord>,
+    ) -> Box<Expr> {
+        static_jsword!(PROP, "prop");
+        self.helper_call(
+            DUMMY_SP,
+            &PROP,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                args.add_str(name);
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, value));
+                    args.add_expr(deps_expr(self, deps));
                     if let Some(sig) = sig {
                         args.add_expr(ident_expr(sig));
                     }
@@ -168,18 +383,47 @@ impl TrContext {
         &self,
         node_name: &JsWord,
         value: Box<Expr>,
-        deps: Option<&Vec<JsWord>>,
+        deps: Option<&Vec<Dep>>,
         sig: Option<&JsWord>,
     ) -> Box<Expr> {
         static_jsword!(ATTRS, "attrs");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &ATTRS,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(ident_expr(node_name));
                 if let Some(deps) = deps {
                     args.add_expr(arrow_short_expr(None, value));
-                    args.add_expr(deps_expr(deps));
+                    args.add_expr(deps_expr(self, deps));
+                    if let Some(sig) = sig {
+                        args.add_expr(ident_expr(sig));
+                    }
+                } else {
+                    args.add_expr(value);
+                }
+            })),
+        )
+    }
+
+    /// Toggles the class token keeping the other ones, e.g. set by third-party code
+    pub fn toggle_class(
+        &self,
+        node_name: &JsWord,
+        token: &str,
+        value: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+        sig: Option<&JsWord>,
+    ) -> Box<Expr> {
+        static_jsword!(TOGGLE_CLASS, "toggleClass");
+        self.helper_call(
+            DUMMY_SP,
+            &TOGGLE_CLASS,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                args.add_str(token);
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, value));
+                    args.add_expr(deps_expr(self, deps));
                     if let Some(sig) = sig {
                         args.add_expr(ident_expr(sig));
                     }
@@ -190,10 +434,133 @@ impl TrContext {
         )
     }
 
-    pub fn element(&self, html: Option<Box<Expr>>, func: Option<Box<Expr>>) -> Box<Expr> {
+    /// Sets the single style property keeping the other ones
+    pub fn style(
+        &self,
+        node_name: &JsWord,
+        name: &str,
+        value: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+        sig: Option<&JsWord>,
+    ) -> Box<Expr> {
+        static_jsword!(STYLE, "style");
+        self.helper_call(
+            DUMMY_SP,
+            &STYLE,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                args.add_str(name);
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, value));
+                    args.add_expr(deps_expr(self, deps));
+                    if let Some(sig) = sig {
+                        args.add_expr(ident_expr(sig));
+                    }
+                } else {
+                    args.add_expr(value);
+                }
+            })),
+        )
+    }
+
+    /// Merges the plain `class` or `style` attribute with the directives of the element
+    /// by adding and removing its class tokens or style declarations
+    pub fn merge_attr(
+        &self,
+        node_name: &JsWord,
+        name: &str,
+        value: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+        sig: Option<&JsWord>,
+    ) -> Box<Expr> {
+        static_jsword!(MERGE_CLASS, "mergeClass");
+        static_jsword!(MERGE_STYLE, "mergeStyle");
+        self.helper_call(
+            DUMMY_SP,
+            if name == "style" {
+                &MERGE_STYLE
+            } else {
+                &MERGE_CLASS
+            },
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, value));
+                    args.add_expr(deps_expr(self, deps));
+                    if let Some(sig) = sig {
+                        args.add_expr(ident_expr(sig));
+                    }
+                } else {
+                    args.add_expr(value);
+                }
+            })),
+        )
+    }
+
+    /// Passes the element to the ref object or callback, which are cleared on unmount
+    pub fn node_ref(
+        &self,
+        node_name: &JsWord,
+        value: Box<Expr>,
+        deps: Option<&Vec<Dep>>,
+        sig: &JsWord,
+    ) -> Box<Expr> {
+        static_jsword!(REF, "ref");
+        self.helper_call(
+            DUMMY_SP,
+            &REF,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, value));
+                    args.add_expr(deps_expr(self, deps));
+                } else {
+                    args.add_expr(value);
+                    args.add_expr(null_expr());
+                }
+                args.add_expr(ident_expr(sig));
+            })),
+        )
+    }
+
+    /// Calls the action with the element, which is updated on changes and destroyed on unmount
+    pub fn action(
+        &self,
+        node_name: &JsWord,
+        action: Box<Expr>,
+        params: Option<Box<Expr>>,
+        deps: Option<&Vec<Dep>>,
+        sig: &JsWord,
+    ) -> Box<Expr> {
+        static_jsword!(ACTION, "action");
+        self.helper_call(
+            DUMMY_SP,
+            &ACTION,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(ident_expr(node_name));
+                args.add_expr(action);
+                let params = params.unwrap_or_else(undefined_expr);
+                if let Some(deps) = deps {
+                    args.add_expr(arrow_short_expr(None, params));
+                    args.add_expr(deps_expr(self, deps));
+                } else {
+                    args.add_expr(params);
+                    args.add_expr(null_expr());
+                }
+                args.add_expr(ident_expr(sig));
+            })),
+        )
+    }
+
+    pub fn element(
+        &self,
+        span: Span,
+        html: Option<Box<Expr>>,
+        func: Option<Box<Expr>>,
+    ) -> Box<Expr> {
         static_jsword!(EL, "el");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            span,
             &EL,
             Some(ArgsBuilder::build_using(|args| {
                 if let Some(html) = html {
@@ -208,10 +575,31 @@ impl TrContext {
         )
     }
 
+    /// The same as `element`, but the function gets the element itself to adopt it
+    pub fn adopt(&self, span: Span, html: Option<Box<Expr>>, func: Option<Box<Expr>>) -> Box<Expr> {
+        static_jsword!(ADOPT, "adopt");
+        self.helper_call(
+            span,
+            &ADOPT,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_expr(html.unwrap_or_else(|| Box::from("")));
+                if let Some(func) = func {
+                    args.add_expr(func);
+                }
+            })),
+        )
+    }
+
+    /// Skips the server-rendered content at the insertion point
+    pub fn anchor(&self, node: Box<Expr>) -> Box<Expr> {
+        static_jsword!(ANCHOR, "anchor");
+        self.helper_call(DUMMY_SP, &ANCHOR, Some(ArgsBuilder::from(node).build()))
+    }
+
     pub fn insert(&self, expr: Box<Expr>, target_name: &JsWord, anchor_name: &JsWord) -> Box<Expr> {
         static_jsword!(INSERT, "insert");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &INSERT,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(expr)
@@ -223,8 +611,8 @@ impl TrContext {
 
     pub fn unmount_on(&self, expr: Box<Expr>) -> Box<Expr> {
         static_jsword!(UNMOUNT_ON, "unmountOn");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &UNMOUNT_ON,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(ident_expr(&self.unmount_sig_name))
@@ -238,31 +626,87 @@ impl TrContext {
         target_name: &JsWord,
         event_name: &str,
         cb: Box<Expr>,
-        deps: Option<&Vec<JsWord>>,
+        deps: Option<&Vec<Dep>>,
         sig: Option<&JsWord>,
+        modifiers: &[String],
     ) -> Box<Expr> {
         static_jsword!(LISTEN, "listen");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &LISTEN,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(ident_expr(&target_name.clone()))
                     .add_expr(Box::from(Str::from(event_name)))
                     .add_expr(cb);
-                if let Some(deps) = deps {
-                    args.add_expr(deps_expr(deps));
-                    if let Some(sig) = sig {
-                        args.add_expr(ident_expr(sig));
+                if modifiers.is_empty() {
+                    if let Some(deps) = deps {
+                        args.add_expr(deps_expr(self, deps));
+                        if let Some(sig) = sig {
+                            args.add_expr(ident_expr(sig));
+                        }
                     }
+                } else {
+                    match (deps, sig) {
+                        (Some(deps), Some(sig)) => {
+                            args.add_expr(deps_expr(self, deps));
+                            args.add_expr(ident_expr(sig));
+                        }
+                        _ => {
+                            args.add_expr(null_expr());
+                            args.add_expr(null_expr());
+                        }
+                    };
+                    // The listener options, e.g. `{ prevent: true, once: true }`
+                    let mut options = ObjLitBuilder::default();
+                    for m in modifiers.iter() {
+                        options.add_key(PropName::from(ident(&m.as_str().into())), Box::from(true));
+                    }
+                    args.add_expr(options.build_expr());
                 }
             })),
         )
     }
 
-    pub fn cmp(&self, name: Box<Expr>, props: Box<Expr>) -> Box<Expr> {
+    /// Defines the view as a custom element
+    pub fn define_element(
+        &self,
+        tag_name: &str,
+        view: &JsWord,
+        params: &[JsWord],
+        shadow: bool,
+    ) -> Box<Expr> {
+        static_jsword!(DEFINE_ELEMENT, "defineElement");
+        self.helper_call(
+            DUMMY_SP,
+            &DEFINE_ELEMENT,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_str(tag_name);
+                args.add_expr(ident_expr(view));
+                args.add_expr(Box::from(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: params
+                        .iter()
+                        .map(|p| {
+                            Some(ExprOrSpread {
+                                spread: None,
+                                expr: Box::from(Str::from(p.clone())),
+                            })
+                        })
+                        .collect(),
+                }));
+                if shadow {
+                    let mut options = ObjLitBuilder::default();
+                    options.add_key(PropName::from(ident(&"shadow".into())), Box::from(true));
+                    args.add_expr(options.build_expr());
+                }
+            })),
+        )
+    }
+
+    pub fn cmp(&self, span: Span, name: Box<Expr>, props: Box<Expr>) -> Box<Expr> {
         static_jsword!(CMP, "cmp");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            span,
             &CMP,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr(name);
@@ -273,8 +717,8 @@ impl TrContext {
 
     pub fn view(&self, params: Vec<Ident>, body: Box<BlockStmtOrExpr>) -> Box<Expr> {
         static_jsword!(VIEW, "view");
-        obj_method_call(
-            ident_expr(&self.lib_name),
+        self.helper_call(
+            DUMMY_SP,
             &VIEW,
             Some(ArgsBuilder::build_using(|args| {
                 args.add_expr({
@@ -304,3 +748,82 @@ impl TrContext {
         )
     }
 }
+
+/// Server-side rendering helpers
+impl TrContext {
+    /// Marks the concatenated string as a ready-to-use markup
+    pub fn html(&self, span: Span, value: Box<Expr>) -> Box<Expr> {
+        static_jsword!(HTML, "html");
+        self.helper_call(span, &HTML, Some(ArgsBuilder::from(value).build()))
+    }
+
+    /// Renders an insertable value escaping everything but markup
+    pub fn to_html(&self, value: Box<Expr>) -> Box<Expr> {
+        static_jsword!(TO_HTML, "toHtml");
+        self.helper_call(DUMMY_SP, &TO_HTML, Some(ArgsBuilder::from(value).build()))
+    }
+
+    pub fn attr_to_html(&self, name: &str, value: Box<Expr>) -> Box<Expr> {
+        static_jsword!(ATTR_TO_HTML, "attrToHtml");
+        self.helper_call(
+            DUMMY_SP,
+            &ATTR_TO_HTML,
+            Some(ArgsBuilder::build_using(|args| {
+                args.add_str(name).add_expr(value);
+            })),
+        )
+    }
+
+    pub fn attrs_to_html(&self, values: Box<Expr>) -> Box<Expr> {
+        static_jsword!(ATTRS_TO_HTML, "attrsToHtml");
+        self.helper_call(
+            DUMMY_SP,
+            &ATTRS_TO_HTML,
+            Some(ArgsBuilder::from(values).build()),
+        )
+    }
+
+    /// Renders the `class` attribute from `[token, on]` pairs
+    pub fn class_to_html(&self, tokens: Vec<(Box<Expr>, Box<Expr>)>) -> Box<Expr> {
+        static_jsword!(CLASS_TO_HTML, "classToHtml");
+        self.helper_call(
+            DUMMY_SP,
+            &CLASS_TO_HTML,
+            Some(ArgsBuilder::from(pairs_expr(tokens)).build()),
+        )
+    }
+
+    /// Renders the `style` attribute from `[name, value]` pairs, where the `null`
+    /// name means the value has raw declarations
+    pub fn style_to_html(&self, entries: Vec<(Box<Expr>, Box<Expr>)>) -> Box<Expr> {
+        static_jsword!(STYLE_TO_HTML, "styleToHtml");
+        self.helper_call(
+            DUMMY_SP,
+            &STYLE_TO_HTML,
+            Some(ArgsBuilder::from(pairs_expr(entries)).build()),
+        )
+    }
+
+    pub fn server_view(&self, params: Vec<Ident>, body: Box<BlockStmtOrExpr>) -> Box<Expr> {
+        static_jsword!(SERVER_VIEW, "serverView");
+        self.helper_call(
+            DUMMY_SP,
+            &SERVER_VIEW,
+            Some(ArgsBuilder::build_using(|args| {
+                let model = || {
+                    let mut obj = ObjLitBuilder::default();
+                    for p in params.iter() {
+                        obj.add_shorthand(ident(&p.sym));
+                    }
+                    obj.build_expr()
+                };
+                args.add_expr(model())
+                    .add_expr(arrow_expr(Some(vec![Pat::from(model())]), body));
+            })),
+        )
+    }
+}
+
+fn pairs_expr(pairs: Vec<(Box<Expr>, Box<Expr>)>) -> Box<Expr> {
+    array_expr(pairs.into_iter().map(|(a, b)| array_expr([a, b])))
+}
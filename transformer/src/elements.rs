@@ -0,0 +1,155 @@
+use swc_core::{
+    common::{comments::SingleThreadedComments, BytePos, Span, Spanned},
+    ecma::{ast::*, atoms::JsWord},
+};
+
+use super::errors::{codes, SpanError};
+
+const MARKER: &str = "@element";
+const SHADOW: &str = "shadow";
+
+/// A view defined as a custom element by the `element` output mode
+pub struct ElementDef {
+    pub tag_name: String,
+    /// Renders the view into the open shadow root instead of the element itself
+    pub shadow: bool,
+    /// The view function, which is unknown for the anonymous default export until it's named
+    pub view: Option<JsWord>,
+    pub params: Vec<JsWord>,
+}
+
+impl ElementDef {
+    /// Uses the `/* @element my-counter shadow */` comment leading any of the positions
+    /// if given, or makes the tag name from the view name otherwise.
+    pub fn new(
+        comments: &SingleThreadedComments,
+        positions: &[BytePos],
+        view_name: Option<&str>,
+        view: Option<JsWord>,
+        func: &Function,
+    ) -> Result<Self, SpanError> {
+        let words: Vec<String> = positions
+            .iter()
+            .find_map(|pos| marker_words(comments, *pos))
+            .unwrap_or_default();
+        let shadow = words.iter().any(|w| w == SHADOW);
+        let tag_name = words
+            .into_iter()
+            .find(|w| w != SHADOW)
+            .or_else(|| view_name.map(kebab_case))
+            .unwrap_or_default();
+        if !is_valid_tag_name(&tag_name) {
+            return Err(SpanError::new(
+                func.span,
+                &format!(
+                    "\"{tag_name}\" is not a valid custom element name, set one containing a hyphen via the comment like `/* @element my-view */`"
+                ),
+                codes::INVALID_ELEMENT,
+            ));
+        }
+        Ok(Self {
+            tag_name,
+            shadow,
+            view,
+            params: param_names(func)?,
+        })
+    }
+}
+
+fn marker_words(comments: &SingleThreadedComments, pos: BytePos) -> Option<Vec<String>> {
+    comments.with_leading(pos, |list| {
+        list.iter().find_map(|c| {
+            let rest = c.text.trim().strip_prefix(MARKER)?;
+            (rest.is_empty() || rest.starts_with(char::is_whitespace))
+                .then(|| rest.split_whitespace().map(str::to_string).collect())
+        })
+    })
+}
+
+/// Elements are created without arguments and their parameters are set by name,
+/// so destructured ones aren't supported.
+fn param_names(func: &Function) -> Result<Vec<JsWord>, SpanError> {
+    let err = |span: Span| {
+        SpanError::new(
+            span,
+            "Custom element views expect plain parameters, e.g. `(count, label = \"\") => ...`",
+            codes::INVALID_ELEMENT,
+        )
+    };
+    func.params
+        .iter()
+        .map(|p| match &p.pat {
+            Pat::Ident(binding) => Ok(binding.id.sym.clone()),
+            Pat::Assign(AssignPat { left, .. }) => match &**left {
+                Pat::Ident(binding) => Ok(binding.id.sym.clone()),
+                _ => Err(err(p.pat.span())),
+            },
+            _ => Err(err(p.pat.span())),
+        })
+        .collect()
+}
+
+/// `MyCounter`, `myCounter` or `my_counter` become `my-counter`
+pub fn kebab_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 2);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !result.ends_with('-') {
+                result.push('-');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else if c == '_' {
+            result.push('-');
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// See https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name
+fn is_valid_tag_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.contains('-')
+        && name
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '-' | '.' | '_') || !c.is_ascii())
+        && !matches!(
+            name,
+            "annotation-xml"
+                | "color-profile"
+                | "font-face"
+                | "font-face-src"
+                | "font-face-uri"
+                | "font-face-format"
+                | "font-face-name"
+                | "missing-glyph"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_kebab_case() {
+        assert_eq!(kebab_case("MyCounter"), "my-counter");
+        assert_eq!(kebab_case("myCounter"), "my-counter");
+        assert_eq!(kebab_case("my_counter"), "my-counter");
+        assert_eq!(kebab_case("my-counter"), "my-counter");
+        assert_eq!(kebab_case("Counter"), "counter");
+    }
+
+    #[test]
+    fn test_is_valid_tag_name() {
+        assert!(is_valid_tag_name("my-counter"));
+        assert!(is_valid_tag_name("x-1"));
+        assert!(!is_valid_tag_name("counter"));
+        assert!(!is_valid_tag_name("My-counter"));
+        assert!(!is_valid_tag_name("-counter"));
+        assert!(!is_valid_tag_name("my counter"));
+        assert!(!is_valid_tag_name("font-face"));
+        assert!(!is_valid_tag_name(""));
+    }
+}
@@ -1,15 +1,225 @@
-use swc_core::common::Span;
+use std::{error::Error, fmt};
+
+use swc_core::common::{
+    errors::{DiagnosticBuilder, DiagnosticId, Emitter, Level, HANDLER},
+    sync::{Lock, Lrc},
+    FileName, SourceMap, Span,
+};
 
 pub struct SpanError {
     pub span: Span,
     pub msg: String,
+    pub code: &'static str,
 }
 
 impl SpanError {
-    pub fn new(span: Span, msg: &str) -> Self {
+    pub fn new(span: Span, msg: &str, code: &'static str) -> Self {
         Self {
             span,
             msg: msg.to_string(),
+            code,
+        }
+    }
+
+    /// Reports the error to the current handler, so the transformation
+    /// could go on and collect the rest of errors.
+    pub fn emit(self) {
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err_with_code(
+                    self.span,
+                    &self.msg,
+                    DiagnosticId::Error(self.code.to_string()),
+                )
+                .emit();
+        });
+    }
+}
+
+pub mod codes {
+    pub const SYNTAX: &str = "syntax";
+    pub const INVALID_NODE: &str = "invalid-node";
+    pub const INVALID_NESTING: &str = "invalid-nesting";
+    pub const INVALID_BINDING: &str = "invalid-binding";
+    pub const INVALID_PRAGMA: &str = "invalid-pragma";
+    pub const INVALID_ASSIGNMENT: &str = "invalid-assignment";
+    pub const INVALID_ELEMENT: &str = "invalid-element";
+    pub const INVALID_REF: &str = "invalid-ref";
+    pub const INVALID_ACTION: &str = "invalid-action";
+    pub const INVALID_DIRECTIVE: &str = "invalid-directive";
+    pub const INVALID_COMPONENT: &str = "invalid-component";
+    pub const INTERNAL: &str = "internal";
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        })
+    }
+}
+
+/// A position in a source file: the line is 1-based and the column is 0-based.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub code: Option<String>,
+    pub file: Option<String>,
+    pub range: Option<Range>,
+}
+
+impl Diagnostic {
+    pub fn internal(message: impl ToString) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.to_string(),
+            code: Some(codes::INTERNAL.to_string()),
+            file: None,
+            range: None,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
         }
+        if let Some(range) = &self.range {
+            write!(f, "{}:{}:", range.start.line, range.start.column + 1)?;
+        }
+        if self.file.is_some() || self.range.is_some() {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}", self.severity)?;
+        if let Some(code) = &self.code {
+            write!(f, "[{code}]")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Transformation errors along with the warnings emitted before the failure.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl From<Diagnostic> for Diagnostics {
+    fn from(value: Diagnostic) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.0.iter().map(|d| d.to_string()).collect();
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+impl Error for Diagnostics {}
+
+/// Collects swc diagnostics instead of printing them to a terminal.
+pub struct Collector {
+    cm: Lrc<SourceMap>,
+    list: Lrc<Lock<Vec<Diagnostic>>>,
+}
+
+impl Collector {
+    pub fn new(cm: Lrc<SourceMap>) -> (Self, Lrc<Lock<Vec<Diagnostic>>>) {
+        let list: Lrc<Lock<Vec<Diagnostic>>> = Default::default();
+        (
+            Self {
+                cm,
+                list: list.clone(),
+            },
+            list,
+        )
+    }
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let severity = match db.level {
+            Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => Severity::Error,
+            Level::Warning => Severity::Warning,
+            Level::Note | Level::Help | Level::FailureNote => Severity::Note,
+            Level::Cancelled => return,
+        };
+        let code = db.code.as_ref().map(|code| match code {
+            DiagnosticId::Error(s) => s.clone(),
+            DiagnosticId::Lint(s) => s.clone(),
+        });
+        let (file, range) = match db.span.primary_span() {
+            Some(span) if !span.is_dummy() => {
+                let start = self.cm.lookup_char_pos(span.lo);
+                let end = self.cm.lookup_char_pos(span.hi);
+                let file = match &start.file.name {
+                    FileName::Anon => None,
+                    FileName::Custom(name) => Some(name.clone()),
+                    name => Some(name.to_string()),
+                };
+                let range = Range {
+                    start: Position {
+                        line: start.line,
+                        column: start.col.0,
+                    },
+                    end: Position {
+                        line: end.line,
+                        column: end.col.0,
+                    },
+                };
+                (file, Some(range))
+            }
+            _ => (None, None),
+        };
+        self.list.lock().push(Diagnostic {
+            severity,
+            message: db.message(),
+            code,
+            file,
+            range,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut d = Diagnostic::internal("Oops");
+        assert_eq!(d.to_string(), "error[internal]: Oops");
+        d.file = Some("input.jsx".to_string());
+        d.range = Some(Range {
+            start: Position { line: 2, column: 4 },
+            end: Position { line: 2, column: 8 },
+        });
+        d.severity = Severity::Warning;
+        d.code = None;
+        assert_eq!(d.to_string(), "input.jsx:2:5: warning: Oops");
     }
 }
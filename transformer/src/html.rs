@@ -0,0 +1,393 @@
+pub fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\u{A0}' => out.push_str("&nbsp;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes a value to be placed inside double quotes.
+pub fn escape_attr(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\u{A0}' => out.push_str("&nbsp;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn is_void(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Maps an event handler attribute to the event name: `onclick` and React-style
+/// `onClick` both listen to `click`, but `one`, `online` or `onValue` aren't known events.
+pub fn event_name(attr_name: &str) -> Option<String> {
+    any_event_name(attr_name).filter(|name| is_event(name))
+}
+
+/// Maps an `on*` attribute to the event name without checking if it's known,
+/// e.g. `onmyevent` is `myevent`.
+pub fn any_event_name(attr_name: &str) -> Option<String> {
+    let name = attr_name
+        .strip_prefix("on")
+        .filter(|name| !name.is_empty())?;
+    let name = if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        match name {
+            "DoubleClick" => "dblclick".to_string(),
+            _ => name.to_ascii_lowercase(),
+        }
+    } else {
+        name.to_string()
+    };
+    Some(name)
+}
+
+/// Maps a style property to the CSS one: `fontSize` and `WebkitTransition` become
+/// `font-size` and `-webkit-transition`, while `font-size` or `--main-color` are kept.
+/// Lowercase vendor prefixes like `msTransition` get the leading dash as well.
+pub fn css_property_name(name: &str) -> String {
+    if name.starts_with("--") {
+        return name.to_string();
+    }
+    let mut result = String::with_capacity(name.len() + 2);
+    let vendor = ["ms", "moz", "webkit"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+    });
+    if vendor {
+        result.push('-');
+    }
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('-');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Known DOM events including the window ones handled by `<body>`
+fn is_event(name: &str) -> bool {
+    matches!(
+        name,
+        "abort"
+            | "afterprint"
+            | "animationcancel"
+            | "animationend"
+            | "animationiteration"
+            | "animationstart"
+            | "auxclick"
+            | "beforeinput"
+            | "beforeprint"
+            | "beforetoggle"
+            | "beforeunload"
+            | "blur"
+            | "cancel"
+            | "canplay"
+            | "canplaythrough"
+            | "change"
+            | "click"
+            | "close"
+            | "compositionend"
+            | "compositionstart"
+            | "compositionupdate"
+            | "contextmenu"
+            | "copy"
+            | "cuechange"
+            | "cut"
+            | "dblclick"
+            | "drag"
+            | "dragend"
+            | "dragenter"
+            | "dragleave"
+            | "dragover"
+            | "dragstart"
+            | "drop"
+            | "durationchange"
+            | "emptied"
+            | "ended"
+            | "error"
+            | "focus"
+            | "focusin"
+            | "focusout"
+            | "formdata"
+            | "gotpointercapture"
+            | "hashchange"
+            | "input"
+            | "invalid"
+            | "keydown"
+            | "keypress"
+            | "keyup"
+            | "languagechange"
+            | "load"
+            | "loadeddata"
+            | "loadedmetadata"
+            | "loadstart"
+            | "lostpointercapture"
+            | "message"
+            | "messageerror"
+            | "mousedown"
+            | "mouseenter"
+            | "mouseleave"
+            | "mousemove"
+            | "mouseout"
+            | "mouseover"
+            | "mouseup"
+            | "offline"
+            | "online"
+            | "pagehide"
+            | "pageshow"
+            | "paste"
+            | "pause"
+            | "play"
+            | "playing"
+            | "pointercancel"
+            | "pointerdown"
+            | "pointerenter"
+            | "pointerleave"
+            | "pointermove"
+            | "pointerout"
+            | "pointerover"
+            | "pointerup"
+            | "popstate"
+            | "progress"
+            | "ratechange"
+            | "rejectionhandled"
+            | "reset"
+            | "resize"
+            | "scroll"
+            | "scrollend"
+            | "securitypolicyviolation"
+            | "seeked"
+            | "seeking"
+            | "select"
+            | "selectionchange"
+            | "selectstart"
+            | "slotchange"
+            | "stalled"
+            | "storage"
+            | "submit"
+            | "suspend"
+            | "timeupdate"
+            | "toggle"
+            | "touchcancel"
+            | "touchend"
+            | "touchmove"
+            | "touchstart"
+            | "transitioncancel"
+            | "transitionend"
+            | "transitionrun"
+            | "transitionstart"
+            | "unhandledrejection"
+            | "unload"
+            | "volumechange"
+            | "waiting"
+            | "wheel"
+    )
+}
+
+/// Attributes only setting initial values, so dynamic values should be
+/// assigned to the corresponding DOM properties instead.
+pub fn is_prop(attr_name: &str) -> bool {
+    matches!(
+        attr_name,
+        "value" | "checked" | "selected" | "indeterminate" | "muted" | "innerHTML" | "textContent"
+    )
+}
+
+/// Elements implicitly closing an open `<p>`
+fn closes_p(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "details"
+            | "dialog"
+            | "div"
+            | "dl"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "header"
+            | "hgroup"
+            | "hr"
+            | "main"
+            | "menu"
+            | "nav"
+            | "ol"
+            | "p"
+            | "pre"
+            | "section"
+            | "table"
+            | "ul"
+    )
+}
+
+/// Checks if the HTML parser keeps the child inside the parent, otherwise
+/// returns the reason. Such templates produce another DOM tree, so the
+/// transformed code would look for nodes at wrong places.
+pub fn check_nesting(parent: &str, child: &str) -> Result<(), String> {
+    let allowed: Option<&[&str]> = match parent {
+        "table" => Some(&["caption", "colgroup", "thead", "tbody", "tfoot"]),
+        "thead" | "tbody" | "tfoot" => Some(&["tr"]),
+        "tr" => Some(&["td", "th"]),
+        "colgroup" => Some(&["col"]),
+        _ => None,
+    };
+    if let Some(allowed) = allowed {
+        if allowed.contains(&child) || matches!(child, "script" | "template") {
+            return Ok(());
+        }
+        let expected = allowed
+            .iter()
+            .map(|s| format!("<{s}>"))
+            .collect::<Vec<String>>()
+            .join(", ");
+        return Err(format!(
+            "<{child}> cannot be a child of <{parent}>, expected one of: {expected}"
+        ));
+    }
+    let expected_parent: Option<&[&str]> = match child {
+        "tr" => Some(&["thead", "tbody", "tfoot"]),
+        "td" | "th" => Some(&["tr"]),
+        "caption" | "colgroup" | "thead" | "tbody" | "tfoot" => Some(&["table"]),
+        "col" => Some(&["colgroup"]),
+        _ => None,
+    };
+    if let Some(expected) = expected_parent {
+        let expected = expected
+            .iter()
+            .map(|s| format!("<{s}>"))
+            .collect::<Vec<String>>()
+            .join(" or ");
+        return Err(format!(
+            "<{child}> cannot be a child of <{parent}>, expected {expected}"
+        ));
+    }
+    let closes_parent = match parent {
+        "p" => closes_p(child),
+        "a" | "button" | "form" | "li" | "option" => parent == child,
+        "dt" | "dd" => matches!(child, "dt" | "dd"),
+        _ => false,
+    };
+    if closes_parent {
+        return Err(format!(
+            "<{child}> cannot be a child of <{parent}>, the HTML parser would close <{parent}> before it"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape_text("a & b < c > d \"e\""),
+            "a &amp; b &lt; c &gt; d \"e\""
+        );
+        assert_eq!(escape_text("\u{a0}text"), "&nbsp;text");
+        assert_eq!(
+            escape_attr("say \"hi\" & <go>"),
+            "say &quot;hi&quot; &amp; <go>"
+        );
+    }
+
+    #[test]
+    fn test_event_name() {
+        assert_eq!(event_name("onclick").as_deref(), Some("click"));
+        assert_eq!(event_name("onClick").as_deref(), Some("click"));
+        assert_eq!(event_name("onMouseDown").as_deref(), Some("mousedown"));
+        assert_eq!(event_name("onDoubleClick").as_deref(), Some("dblclick"));
+        assert_eq!(event_name("ononline").as_deref(), Some("online"));
+        assert_eq!(event_name("onClickOutside"), None);
+        assert_eq!(event_name("onValue"), None);
+        assert_eq!(event_name("one"), None);
+        assert_eq!(event_name("online"), None);
+        assert_eq!(event_name("on"), None);
+        assert_eq!(event_name("title"), None);
+    }
+
+    #[test]
+    fn test_any_event_name() {
+        assert_eq!(any_event_name("onmyevent").as_deref(), Some("myevent"));
+        assert_eq!(any_event_name("onValue").as_deref(), Some("value"));
+        assert_eq!(any_event_name("onClick").as_deref(), Some("click"));
+        assert_eq!(any_event_name("on"), None);
+        assert_eq!(any_event_name("title"), None);
+    }
+
+    #[test]
+    fn test_css_property_name() {
+        assert_eq!(css_property_name("color"), "color");
+        assert_eq!(css_property_name("fontSize"), "font-size");
+        assert_eq!(css_property_name("font-size"), "font-size");
+        assert_eq!(css_property_name("WebkitTransition"), "-webkit-transition");
+        assert_eq!(css_property_name("msTransition"), "-ms-transition");
+        assert_eq!(css_property_name("MozAppearance"), "-moz-appearance");
+        assert_eq!(css_property_name("webkitLineClamp"), "-webkit-line-clamp");
+        assert_eq!(css_property_name("msx"), "msx");
+        assert_eq!(css_property_name("--mainColor"), "--mainColor");
+    }
+
+    #[test]
+    fn test_nesting() {
+        assert!(check_nesting("div", "p").is_ok());
+        assert!(check_nesting("p", "span").is_ok());
+        assert!(check_nesting("p", "div").is_err());
+        assert!(check_nesting("p", "p").is_err());
+        assert!(check_nesting("a", "a").is_err());
+        assert!(check_nesting("ul", "li").is_ok());
+        assert!(check_nesting("li", "li").is_err());
+        assert!(check_nesting("table", "tbody").is_ok());
+        assert!(check_nesting("table", "tr").is_err());
+        assert!(check_nesting("table", "div").is_err());
+        assert!(check_nesting("tbody", "tr").is_ok());
+        assert!(check_nesting("tr", "td").is_ok());
+        assert!(check_nesting("tr", "span").is_err());
+        assert!(check_nesting("div", "tr").is_err());
+        assert!(check_nesting("div", "td").is_err());
+        assert!(check_nesting("colgroup", "col").is_ok());
+    }
+}
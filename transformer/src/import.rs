@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+pub const DEFAULT_RUNTIME: &str = "viewmill-runtime";
+
+/// How the runtime module is imported into the output.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ImportStyle {
+    /// `import * as viewmill from "viewmill-runtime"`
+    #[default]
+    Namespace,
+    /// `import { el, view } from "viewmill-runtime"` with only the used helpers
+    Named,
+    /// `const viewmill = require("viewmill-runtime")` with other imports and exports
+    /// of the file lowered to CommonJS too, or `import viewmill = require(...)` in
+    /// typescript, which leaves them to `tsc`.
+    Require,
+}
+
+const NAMESPACE: &str = "namespace";
+const NAMED: &str = "named";
+const REQUIRE: &str = "require";
+
+impl FromStr for ImportStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            NAMESPACE => Ok(Self::Namespace),
+            NAMED => Ok(Self::Named),
+            REQUIRE => Ok(Self::Require),
+            _ => Err(format!(
+                "Unknown import style \"{s}\", expected \"{NAMESPACE}\", \"{NAMED}\" or \"{REQUIRE}\""
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "namespace".parse::<ImportStyle>().unwrap(),
+            ImportStyle::Namespace
+        );
+        assert_eq!("Named".parse::<ImportStyle>().unwrap(), ImportStyle::Named);
+        assert_eq!(
+            "require".parse::<ImportStyle>().unwrap(),
+            ImportStyle::Require
+        );
+        assert!("lorem ipsum".parse::<ImportStyle>().is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use regex::Regex;
 use swc_core::{
-    common::{sync::Lazy, DUMMY_SP},
+    common::{sync::Lazy, util::take::Take, Span, Spanned, DUMMY_SP},
     ecma::{ast::*, atoms::*},
 };
 
 use super::{
     context::TrContext,
-    errors::SpanError,
+    errors::{codes, SpanError},
+    html, live,
+    mode::OutputMode,
     scope::{Scope, ScopeItem},
     tr::*,
     utils::*,
@@ -18,15 +20,17 @@ pub fn tr_root_el(
     scope: &Scope,
 ) -> Result<Box<Expr>, SpanError> {
     match ElName::from(&el.opening.name) {
-        ElName::HTML(tag_name) => {
-            let mut builder = ElBuilder::new(&ctx, scope);
+        ElName::Html(tag_name) => {
+            let mut builder = ElBuilder::new(ctx, scope, el.span);
             let container_name = builder.container_name.clone();
-            tr_html_el(
-                &tag_name,
-                el,
-                &mut builder,
-                &NodePath::Root(container_name).first(),
-            )?;
+            let node_path = match ctx.mode() {
+                // The element itself is passed to be adopted
+                OutputMode::Hydrate => NodePath::Root(container_name),
+                OutputMode::Dom | OutputMode::Element | OutputMode::Ssr => {
+                    NodePath::Root(container_name).first()
+                }
+            };
+            tr_html_el(&tag_name, el, &mut builder, &node_path, None)?;
             Ok(builder.build())
         }
         ElName::Custom(name) => tr_cmp(ctx, el, name, scope),
@@ -40,23 +44,11 @@ pub fn tr_root_frag(
 ) -> Result<Box<Expr>, SpanError> {
     let mut elems = vec![];
     for child in frag.children.iter_mut() {
-        let expr = match child {
-            JSXElementChild::JSXText(text) => {
-                let text = tr_child_text(&text.value);
-                if text.is_empty() {
-                    None
-                } else {
-                    Some(Box::from(text))
-                }
-            }
-            JSXElementChild::JSXExprContainer(c) => tr_child_expr_container(ctx, c, scope)?,
-            JSXElementChild::JSXSpreadChild(spread) => Some(tr_child_spread(ctx, spread, scope)?),
-            JSXElementChild::JSXElement(el) => Some(tr_root_el(ctx, el, scope)?),
-            JSXElementChild::JSXFragment(frag) => Some(tr_root_frag(ctx, frag, scope)?),
+        match tr_child_as_expr(ctx, child, scope) {
+            Ok(Some(expr)) => elems.push(Some(ExprOrSpread::from(expr))),
+            Ok(None) => (),
+            Err(err) => err.emit(),
         };
-        if let Some(expr) = expr {
-            elems.push(Some(ExprOrSpread::from(expr)));
-        }
     }
     Ok(Box::from(ArrayLit {
         span: DUMMY_SP,
@@ -68,6 +60,7 @@ fn tr_el_child(
     child: &mut JSXElementChild,
     builder: &mut ElBuilder,
     container_name: &JsWord,
+    container_tag: &str,
     node_path: &NodePath,
 ) -> Result<Option<NodePath>, SpanError> {
     let ctx = &builder.ctx;
@@ -77,7 +70,7 @@ fn tr_el_child(
             if text.is_empty() {
                 Ok(None)
             } else {
-                builder.push_html_str(&text);
+                builder.push_html_str(&html::escape_text(&text));
                 Ok(Some(node_path.clone()))
             }
         }
@@ -100,7 +93,7 @@ fn tr_el_child(
             Ok(Some(path))
         }
         JSXElementChild::JSXElement(el) => {
-            let path = tr_child_el(el, builder, container_name, node_path)?;
+            let path = tr_child_el(el, builder, container_name, container_tag, node_path)?;
             Ok(Some(path))
         }
         JSXElementChild::JSXFragment(frag) => {
@@ -111,12 +104,12 @@ fn tr_el_child(
 }
 
 fn tr_child_text(text: &str) -> String {
-    static NL_WS: &str = r"\n[\n\t\s]*";
+    // Non-breaking spaces are kept as is
+    static NL_WS: &str = r"\n[\n\t\s&&[^\u{A0}]]*";
     static RE_OUTER: Lazy<Regex> =
         Lazy::new(|| Regex::new(&format!("^({NL_WS})|({NL_WS})$")).unwrap());
     static RE_INNER: Lazy<Regex> = Lazy::new(|| Regex::new(NL_WS).unwrap());
-    let text = text.replace('\u{A0}', "&nbsp;");
-    let text = RE_OUTER.replace_all(&text, "").to_string();
+    let text = RE_OUTER.replace_all(text, "").to_string();
     RE_INNER.replace_all(&text, " ").to_string()
 }
 
@@ -127,34 +120,41 @@ fn tr_child_expr_container(
 ) -> Result<Option<Box<Expr>>, SpanError> {
     match &mut c.expr {
         JSXExpr::JSXEmptyExpr(_) => Ok(None),
-        JSXExpr::Expr(expr) => match tr_expr(ctx, expr, scope)? {
-            TrValue::None => match &**expr {
-                Expr::Bin(expr) if expr.op == op!("&&") => {
-                    Ok(Some(Box::new(Expr::Cond(CondExpr {
-                        span: DUMMY_SP,
-                        test: expr.left.clone(),
-                        cons: expr.right.clone(),
-                        alt: null_expr(),
-                    }))))
-                }
-                _ => Ok(Some(expr.clone())),
-            },
-            TrValue::Deps(deps) => match &**expr {
-                Expr::Cond(expr) => Ok(Some(ctx.condition(
-                    expr.test.clone(),
-                    expr.cons.clone(),
-                    expr.alt.clone(),
-                    &deps,
-                ))),
-                Expr::Bin(expr) if expr.op == op!("&&") => Ok(Some(ctx.condition(
-                    expr.left.clone(),
-                    expr.right.clone(),
-                    null_expr(),
-                    &deps,
-                ))),
-                _ => Ok(Some(ctx.expression(expr.clone(), &deps))),
-            },
-        },
+        JSXExpr::Expr(expr) => {
+            // Analyzed before the live idents are replaced with their values
+            let is_primitive = is_primitive_expr(expr, scope);
+            match tr_expr(ctx, expr, scope)? {
+                // Module lives are rendered once by their current values
+                TrValue::Deps(_) if ctx.mode() == OutputMode::Ssr => Ok(Some(expr.clone())),
+                TrValue::None => match &**expr {
+                    Expr::Bin(expr) if expr.op == op!("&&") => {
+                        Ok(Some(Box::new(Expr::Cond(CondExpr {
+                            span: DUMMY_SP,
+                            test: expr.left.clone(),
+                            cons: expr.right.clone(),
+                            alt: null_expr(),
+                        }))))
+                    }
+                    _ => Ok(Some(expr.clone())),
+                },
+                TrValue::Deps(deps) => match &**expr {
+                    Expr::Cond(expr) => Ok(Some(ctx.condition(
+                        expr.test.clone(),
+                        expr.cons.clone(),
+                        expr.alt.clone(),
+                        &deps,
+                    ))),
+                    Expr::Bin(expr) if expr.op == op!("&&") => Ok(Some(ctx.condition(
+                        expr.left.clone(),
+                        expr.right.clone(),
+                        null_expr(),
+                        &deps,
+                    ))),
+                    _ if is_primitive => Ok(Some(ctx.text(expr.clone(), &deps))),
+                    _ => Ok(Some(ctx.expression(expr.clone(), &deps))),
+                },
+            }
+        }
     }
 }
 
@@ -164,6 +164,13 @@ fn tr_child_spread(
     scope: &Scope,
 ) -> Result<Box<Expr>, SpanError> {
     let expr = &mut spread.expr;
+    if let Some(keyed) = tr_keyed_list(ctx, expr, scope)? {
+        return Ok(keyed);
+    }
+    if ctx.mode() == OutputMode::Ssr {
+        tr_expr(ctx, expr, scope)?;
+        return Ok(expr.clone());
+    }
     let deps = match tr_expr(ctx, expr, scope)? {
         TrValue::None => None,
         TrValue::Deps(deps) => Some(deps),
@@ -171,15 +178,120 @@ fn tr_child_spread(
     Ok(ctx.list(expr.clone(), deps.as_ref()))
 }
 
+/// Transforms `items.map((item) => <li key={item.id}>...</li>)` to a keyed list,
+/// so only changed items are re-rendered.
+fn tr_keyed_list(
+    ctx: &TrContext,
+    expr: &mut Box<Expr>,
+    scope: &Scope,
+) -> Result<Option<Box<Expr>>, SpanError> {
+    static MAP: &str = "map";
+    let Expr::Call(call) = &mut **expr else {
+        return Ok(None);
+    };
+    let Callee::Expr(callee) = &call.callee else {
+        return Ok(None);
+    };
+    let Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    }) = &**callee
+    else {
+        return Ok(None);
+    };
+    if &*prop.sym != MAP || call.args.len() != 1 || call.args[0].spread.is_some() {
+        return Ok(None);
+    }
+    let Expr::Arrow(arrow) = &mut *call.args[0].expr else {
+        return Ok(None);
+    };
+    let Some(key) = returned_jsx_el(&mut arrow.body).and_then(take_key_attr) else {
+        return Ok(None);
+    };
+    if ctx.mode() == OutputMode::Ssr {
+        // Keys only matter when reconciling DOM, so the list is rendered as is
+        return Ok(None);
+    }
+    let mut items = obj.clone();
+    let mut result = tr_expr(ctx, &mut items, scope)?;
+    let mut key = Box::new(Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: arrow.params.clone(),
+        body: Box::new(BlockStmtOrExpr::Expr(key)),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    }));
+    result.extend(tr_expr(ctx, &mut key, scope)?);
+    // The returned element is updated on its own
+    let mut render = Box::new(Expr::Arrow(arrow.take()));
+    tr_expr(ctx, &mut render, scope)?;
+    let deps = match result {
+        TrValue::None => None,
+        TrValue::Deps(deps) => Some(deps),
+    };
+    Ok(Some(ctx.keyed(items, key, render, deps.as_ref())))
+}
+
+fn returned_jsx_el(body: &mut BlockStmtOrExpr) -> Option<&mut Box<JSXElement>> {
+    fn unwrap(expr: &mut Expr) -> Option<&mut Box<JSXElement>> {
+        match expr {
+            Expr::Paren(p) => unwrap(&mut p.expr),
+            Expr::JSXElement(el) => Some(el),
+            _ => None,
+        }
+    }
+    match body {
+        BlockStmtOrExpr::Expr(expr) => unwrap(expr),
+        BlockStmtOrExpr::BlockStmt(block) => match block.stmts.as_mut_slice() {
+            [Stmt::Return(ReturnStmt {
+                arg: Some(expr), ..
+            })] => unwrap(expr),
+            _ => None,
+        },
+    }
+}
+
+/// Removes the `key` attribute and returns its value
+fn take_key_attr(el: &mut Box<JSXElement>) -> Option<Box<Expr>> {
+    static KEY: &str = "key";
+    let attrs = &mut el.opening.attrs;
+    let (idx, value) = attrs
+        .iter()
+        .enumerate()
+        .find_map(|(idx, attr)| match attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(name),
+                value: Some(value),
+                ..
+            }) if &*name.sym == KEY => match value {
+                JSXAttrValue::Lit(lit) => Some((idx, Box::from(lit.clone()))),
+                JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                }) => Some((idx, expr.clone())),
+                _ => None,
+            },
+            _ => None,
+        })?;
+    attrs.remove(idx);
+    Some(value)
+}
+
 fn tr_child_el(
     el: &mut Box<JSXElement>,
     builder: &mut ElBuilder,
     container_name: &JsWord,
+    container_tag: &str,
     node_path: &NodePath,
 ) -> Result<NodePath, SpanError> {
     let ctx = &builder.ctx;
     match ElName::from(&el.opening.name) {
-        ElName::HTML(tag_name) => tr_html_el(&tag_name, el, builder, node_path),
+        ElName::Html(tag_name) => {
+            tr_html_el(&tag_name, el, builder, node_path, Some(container_tag))
+        }
         ElName::Custom(name) => {
             let scope = &builder.scope;
             let expr = tr_cmp(ctx, el, name, scope)?;
@@ -227,6 +339,17 @@ fn tr_cmp(
     scope: &Scope,
 ) -> Result<Box<Expr>, SpanError> {
     static_jsword!(CHILDREN, "children");
+    // Views take positional parameters and aren't insertable, unlike components
+    if let Some(ident) = name.as_ident().filter(|ident| scope.is_view(&ident.sym)) {
+        return Err(SpanError::new(
+            el.span,
+            &format!(
+                "View \"{}\" cannot be used as a component, since it takes positional parameters",
+                ident.sym
+            ),
+            codes::INVALID_COMPONENT,
+        ));
+    }
     let mut props = ObjLitBuilder::default();
     for attr in el.opening.attrs.iter_mut() {
         match attr {
@@ -246,7 +369,9 @@ fn tr_cmp(
                                 Some(ident) if scope.is_live(&ident.sym) => expr.clone(),
                                 _ => match tr_expr(ctx, expr, scope)? {
                                     TrValue::None => expr.clone(),
-                                    TrValue::Deps(deps) => ctx.live(expr.clone(), &deps, None),
+                                    TrValue::Deps(deps) => {
+                                        ctx.live(expr.clone(), &deps, None, None)
+                                    }
                                 },
                             },
                         },
@@ -265,9 +390,11 @@ fn tr_cmp(
     }
     let mut children = Vec::with_capacity(el.children.len());
     for child in el.children.iter_mut() {
-        if let Some(expr) = tr_child_as_expr(ctx, child, scope)? {
-            children.push(Some(ExprOrSpread::from(expr)));
-        }
+        match tr_child_as_expr(ctx, child, scope) {
+            Ok(Some(expr)) => children.push(Some(ExprOrSpread::from(expr))),
+            Ok(None) => (),
+            Err(err) => err.emit(),
+        };
     }
     if children.len() > 1 {
         props.add_key(
@@ -278,9 +405,9 @@ fn tr_cmp(
             }),
         );
     } else if let Some(Some(first)) = children.pop() {
-        props.add_key(PropName::Ident(ident(&CHILDREN)), Box::from(first.expr));
+        props.add_key(PropName::Ident(ident(&CHILDREN)), first.expr);
     }
-    Ok(ctx.cmp(name, props.build_expr()))
+    Ok(ctx.cmp(el.span, name, props.build_expr()))
 }
 
 fn tr_html_el(
@@ -288,29 +415,88 @@ fn tr_html_el(
     el: &mut Box<JSXElement>,
     builder: &mut ElBuilder,
     node_path: &NodePath,
+    parent_tag: Option<&str>,
 ) -> Result<NodePath, SpanError> {
+    if let Some(parent_tag) = parent_tag {
+        html::check_nesting(parent_tag, tag_name)
+            .map_err(|msg| SpanError::new(el.span, &msg, codes::INVALID_NESTING))?;
+    }
     builder.push_html_str(&format!("<{tag_name}"));
-    let node_path = builder.push_node_path(&tag_name, node_path);
+    let node_path = builder.push_node_path(tag_name, node_path);
     let node_name = node_path.root();
-    for attr in el.opening.attrs.iter_mut() {
-        match attr {
-            JSXAttrOrSpread::JSXAttr(attr) => tr_el_attr(attr, builder, &node_name)?,
+    let has_directives = el.opening.attrs.iter().any(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => Directive::of(attr).is_some(),
+        JSXAttrOrSpread::SpreadElement(_) => false,
+    });
+    if has_directives && builder.ctx.mode() == OutputMode::Ssr {
+        builder.rendered_directives = Some(RenderedDirectives::default());
+    }
+    builder.has_directives = has_directives;
+    // The value of `<select>` picks one of its options, so it's set after them
+    let defers_value = tag_name == "select" && builder.ctx.mode() != OutputMode::Ssr;
+    let mut deferred_attrs = vec![];
+    for (i, attr) in el.opening.attrs.iter_mut().enumerate() {
+        let result = match attr {
+            JSXAttrOrSpread::JSXAttr(attr) if defers_value && is_value_attr(attr) => {
+                deferred_attrs.push(i);
+                continue;
+            }
+            JSXAttrOrSpread::JSXAttr(attr) => tr_el_attr(attr, builder, &node_name),
             JSXAttrOrSpread::SpreadElement(spread) => {
-                tr_el_spread_attr(spread, builder, &node_name)?
+                tr_el_spread_attr(spread, builder, &node_name)
             }
         };
+        if let Err(err) = result {
+            err.emit();
+        }
     }
-    if let Some(_) = &el.closing {
+    if let Some(rendered) = builder.rendered_directives.take() {
+        if !rendered.classes.is_empty() {
+            let html = builder.ctx.class_to_html(rendered.classes);
+            builder.push_html_expr(*html);
+        }
+        if !rendered.styles.is_empty() {
+            let html = builder.ctx.style_to_html(rendered.styles);
+            builder.push_html_expr(*html);
+        }
+    }
+    let content = builder.content.take();
+    if html::is_void(tag_name) {
+        let has_children = el.children.iter().any(|child| match child {
+            JSXElementChild::JSXText(text) => !tr_child_text(&text.value).is_empty(),
+            _ => true,
+        });
+        if has_children {
+            return Err(SpanError::new(
+                el.span,
+                &format!("<{tag_name}> is a void element and cannot have children"),
+                codes::INVALID_NESTING,
+            ));
+        }
+        builder.push_html_str("/>");
+    } else if let Some(content) = content {
+        // `innerHTML` or `textContent` replace the children
+        builder.push_html_str(">");
+        builder.push_html_expr(*content);
+        builder.push_html_str(&format!("</{tag_name}>"));
+    } else {
         builder.push_html_str(">");
         let mut node_path = node_path.first();
         for child in el.children.iter_mut() {
-            if let Some(path) = tr_el_child(child, builder, &node_name, &node_path)? {
-                node_path = path.next();
-            }
+            match tr_el_child(child, builder, &node_name, tag_name, &node_path) {
+                Ok(Some(path)) => node_path = path.next(),
+                Ok(None) => (),
+                Err(err) => err.emit(),
+            };
         }
         builder.push_html_str(&format!("</{tag_name}>"));
-    } else {
-        builder.push_html_str("/>");
+    }
+    for i in deferred_attrs {
+        if let JSXAttrOrSpread::JSXAttr(attr) = &mut el.opening.attrs[i] {
+            if let Err(err) = tr_el_attr(attr, builder, &node_name) {
+                err.emit();
+            }
+        }
     }
     Ok(node_path)
 }
@@ -320,66 +506,546 @@ fn tr_el_attr(
     builder: &mut ElBuilder,
     node_name: &JsWord,
 ) -> Result<(), SpanError> {
-    static ON: &str = "on";
+    static ON_NS: &str = "on";
+    static PROP_NS: &str = "prop";
+    static BIND_NS: &str = "bind";
+    static REF: &str = "ref";
+    static USE_NS: &str = "use";
+    let is_ref = match &attr.name {
+        JSXAttrName::Ident(ident) => &*ident.sym == REF,
+        JSXAttrName::JSXNamespacedName(nn) => &*nn.ns.sym == REF,
+    };
+    if is_ref {
+        return tr_el_ref(attr, builder, node_name);
+    }
+    if let JSXAttrName::JSXNamespacedName(nn) = &attr.name {
+        if &*nn.ns.sym == USE_NS {
+            let action = nn.name.clone();
+            return tr_el_action(attr, action, builder, node_name);
+        }
+    }
+    if let Some(directive) = Directive::of(attr) {
+        return tr_el_directive(attr, directive, builder, node_name);
+    }
     let ctx = &builder.ctx;
-    let (name, event_name) = match &attr.name {
+    let (name, kind) = match &attr.name {
         JSXAttrName::Ident(ident) => {
             let name = ident.sym.to_string();
-            let event_name = if name.len() > 2 && &name[..2] == ON {
-                Some(name[2..].to_string())
+            let kind = if let Some(event_name) = html::event_name(&name) {
+                AttrKind::Event(event_name, vec![])
+            } else if let Some(event_name) =
+                html::any_event_name(&name).filter(|_| has_expr_value(attr))
+            {
+                // Custom events like `onmyevent={handler}`, while `one="1"` stays an attribute
+                AttrKind::Event(event_name, vec![])
+            } else if html::is_prop(&name) {
+                AttrKind::Prop
             } else {
-                None
+                AttrKind::Attr
             };
-            (name, event_name)
+            (name, kind)
+        }
+        JSXAttrName::JSXNamespacedName(nn) if &*nn.ns.sym == ON_NS => {
+            let (event_name, modifiers) = split_event_modifiers(&nn.name.sym);
+            (str_from_nn(nn), AttrKind::Event(event_name, modifiers))
+        }
+        JSXAttrName::JSXNamespacedName(nn) if &*nn.ns.sym == PROP_NS => {
+            (nn.name.sym.to_string(), AttrKind::ExplicitProp)
         }
-        JSXAttrName::JSXNamespacedName(nn) => (str_from_nn(nn), None),
+        JSXAttrName::JSXNamespacedName(nn) if &*nn.ns.sym == BIND_NS => {
+            return tr_el_bind(attr, &nn.name.sym, builder, node_name);
+        }
+        JSXAttrName::JSXNamespacedName(nn) => (str_from_nn(nn), AttrKind::Attr),
     };
+    if ctx.mode() == OutputMode::Ssr {
+        return tr_el_attr_ssr(attr, &name, kind, builder);
+    }
     if let Some(value) = &mut attr.value {
         match value {
-            JSXAttrValue::Lit(lit) => {
-                builder.push_html_attr(&name, Box::from(lit.clone()));
+            JSXAttrValue::Lit(lit) if matches!(kind, AttrKind::ExplicitProp) => {
+                builder.push_body_expr(ctx.prop(
+                    node_name,
+                    &name,
+                    Box::from(lit.clone()),
+                    None,
+                    None,
+                ));
+            }
+            JSXAttrValue::Lit(Lit::Str(s)) => {
+                builder.push_html_attr(&name, &s.value);
             }
             JSXAttrValue::JSXExprContainer(c) => {
                 match &mut c.expr {
                     JSXExpr::JSXEmptyExpr(_) => (),
                     JSXExpr::Expr(expr) => {
-                        let value = tr_expr(ctx, expr, &builder.scope)?;
-                        if let Some(event_name) = event_name {
-                            let deps = match value {
-                                TrValue::None => None,
-                                TrValue::Deps(deps) => Some(deps),
-                            };
-                            builder.push_body_expr(ctx.listen(
+                        let deps = match tr_expr(ctx, expr, &builder.scope)? {
+                            TrValue::None => None,
+                            TrValue::Deps(deps) => Some(deps),
+                        };
+                        let sig = Some(&builder.unmount_sig_name);
+                        let expr = expr.clone();
+                        builder.push_body_expr(match kind {
+                            AttrKind::Event(event_name, modifiers) => ctx.listen(
                                 node_name,
                                 &event_name,
-                                expr.clone(),
+                                expr,
                                 deps.as_ref(),
-                                Some(&builder.unmount_sig_name),
-                            ));
-                        } else {
-                            let deps = match value {
-                                TrValue::None => None,
-                                TrValue::Deps(deps) => Some(deps),
-                            };
-                            builder.push_body_expr(ctx.attr(
-                                node_name,
-                                &name,
-                                expr.clone(),
-                                deps.as_ref(),
-                                Some(&builder.unmount_sig_name),
-                            ));
-                        }
+                                sig,
+                                &modifiers,
+                            ),
+                            AttrKind::Prop | AttrKind::ExplicitProp => {
+                                ctx.prop(node_name, &name, expr, deps.as_ref(), sig)
+                            }
+                            // Keeps the tokens and properties of the directives
+                            AttrKind::Attr
+                                if builder.has_directives
+                                    && Directive::from_name(&name).is_some() =>
+                            {
+                                ctx.merge_attr(node_name, &name, expr, deps.as_ref(), sig)
+                            }
+                            AttrKind::Attr => ctx.attr(node_name, &name, expr, deps.as_ref(), sig),
+                        });
                     }
                 };
             }
             _ => builder.push_html_str(&format!(" {name}")),
         };
+    } else if matches!(kind, AttrKind::ExplicitProp) {
+        builder.push_body_expr(ctx.prop(node_name, &name, Box::from(true), None, None));
     } else {
         builder.push_html_str(&format!(" {name}"));
     }
     Ok(())
 }
 
+/// Renders the attribute into the markup. Events and explicit properties
+/// don't have any representation there, so they're skipped.
+fn tr_el_attr_ssr(
+    attr: &mut JSXAttr,
+    name: &str,
+    kind: AttrKind,
+    builder: &mut ElBuilder,
+) -> Result<(), SpanError> {
+    if matches!(kind, AttrKind::Event(..) | AttrKind::ExplicitProp) {
+        return Ok(());
+    }
+    let ctx = &builder.ctx;
+    if let (Some(rendered), Some(directive)) =
+        (&mut builder.rendered_directives, Directive::from_name(name))
+    {
+        // Merged with the directives of the element
+        let value = match &mut attr.value {
+            Some(JSXAttrValue::Lit(Lit::Str(s))) => Box::from(s.clone()),
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })) => {
+                tr_expr(ctx, expr, &builder.scope)?;
+                expr.clone()
+            }
+            _ => return Ok(()),
+        };
+        match directive {
+            Directive::Class => rendered.classes.push((value, Box::from(true))),
+            Directive::Style => rendered.styles.push((null_expr(), value)),
+        };
+        return Ok(());
+    }
+    match &mut attr.value {
+        Some(JSXAttrValue::Lit(Lit::Str(s))) => builder.push_html_attr(name, &s.value),
+        Some(JSXAttrValue::JSXExprContainer(c)) => {
+            if let JSXExpr::Expr(expr) = &mut c.expr {
+                tr_expr(ctx, expr, &builder.scope)?;
+                let expr = expr.clone();
+                match (kind, name) {
+                    (AttrKind::Prop, "innerHTML") => {
+                        builder.content = Some(ctx.to_html(ctx.html(DUMMY_SP, expr)));
+                    }
+                    (AttrKind::Prop, "textContent") => {
+                        builder.content = Some(ctx.to_html(expr));
+                    }
+                    _ => builder.push_html_expr(*ctx.attr_to_html(name, expr)),
+                };
+            }
+        }
+        _ => builder.push_html_str(&format!(" {name}")),
+    };
+    Ok(())
+}
+
+/// Two-way binding of a view parameter, e.g. `bind:value={param}`
+fn tr_el_bind(
+    attr: &JSXAttr,
+    name: &str,
+    builder: &mut ElBuilder,
+    node_name: &JsWord,
+) -> Result<(), SpanError> {
+    let event_name = match name {
+        "value" => "input",
+        "checked" => "change",
+        _ => {
+            return Err(SpanError::new(
+                attr.span,
+                &format!(
+                "Unsupported binding \"bind:{name}\", expected \"bind:value\" or \"bind:checked\""
+            ),
+                codes::INVALID_BINDING,
+            ))
+        }
+    };
+    let param = match &attr.value {
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) => expr.as_ident(),
+        _ => None,
+    };
+    let Some(param) = param else {
+        return Err(SpanError::new(
+            attr.span,
+            &format!("\"bind:{name}\" expects a view parameter, e.g. bind:{name}={{param}}"),
+            codes::INVALID_BINDING,
+        ));
+    };
+    let scope = &builder.scope;
+    if !scope.is_param(&param.sym) {
+        let reason = if scope.is_live(&param.sym) {
+            "it's a derived read-only value"
+        } else {
+            "it's not a view parameter"
+        };
+        return Err(SpanError::new(
+            param.span,
+            &format!("Cannot bind to \"{}\", because {reason}", param.sym),
+            codes::INVALID_BINDING,
+        ));
+    }
+    if builder.ctx.mode() == OutputMode::Ssr {
+        // Rendered views don't have live params, so only the value is rendered
+        let value = builder.ctx.attr_to_html(name, ident_expr(&param.sym));
+        builder.push_html_expr(*value);
+        return Ok(());
+    }
+    let ctx = &builder.ctx;
+    let prop = ctx.prop(
+        node_name,
+        name,
+        live::value_of(&param.sym, param.span),
+        Some(&vec![live::Dep::from(&param.sym)]),
+        Some(&builder.unmount_sig_name),
+    );
+    let update = live::set_value(
+        &param.sym,
+        member_expr(
+            ident_expr(node_name),
+            MemberProp::Ident(ident(&name.into())),
+        ),
+    );
+    let listener = ctx.listen(
+        node_name,
+        event_name,
+        arrow_short_expr(None, update),
+        None,
+        None,
+        &[],
+    );
+    builder.push_body_expr(prop);
+    builder.push_body_expr(listener);
+    Ok(())
+}
+
+/// Passes the element to the ref object or callback like `ref={input}` or
+/// its shorthand `ref:input`. Rendered views don't have elements, so they're skipped.
+fn tr_el_ref(
+    attr: &mut JSXAttr,
+    builder: &mut ElBuilder,
+    node_name: &JsWord,
+) -> Result<(), SpanError> {
+    let value = match (&attr.name, &attr.value) {
+        (JSXAttrName::JSXNamespacedName(nn), None) if !nn.name.sym.contains('-') => {
+            Some(Box::from(nn.name.clone()))
+        }
+        (
+            JSXAttrName::Ident(_),
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })),
+        ) => Some(expr.clone()),
+        _ => None,
+    };
+    let Some(mut value) = value else {
+        return Err(SpanError::new(
+            attr.span,
+            "Expected a ref object or callback, e.g. `ref={input}` or `ref:input`",
+            codes::INVALID_REF,
+        ));
+    };
+    let ctx = &builder.ctx;
+    if ctx.mode() == OutputMode::Ssr {
+        return Ok(());
+    }
+    let deps = match tr_expr(ctx, &mut value, &builder.scope)? {
+        TrValue::None => None,
+        TrValue::Deps(deps) => Some(deps),
+    };
+    let expr = ctx.node_ref(node_name, value, deps.as_ref(), &builder.unmount_sig_name);
+    builder.push_body_expr(expr);
+    Ok(())
+}
+
+/// Calls the action like `use:tooltip={options}` with the element, so it's
+/// updated with the options on their changes and destroyed on unmount.
+fn tr_el_action(
+    attr: &mut JSXAttr,
+    action: Ident,
+    builder: &mut ElBuilder,
+    node_name: &JsWord,
+) -> Result<(), SpanError> {
+    let params = match &attr.value {
+        None => None,
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) => Some(expr.clone()),
+        Some(JSXAttrValue::Lit(lit)) => Some(Box::from(lit.clone())),
+        _ => {
+            return Err(SpanError::new(
+                attr.span,
+                "Expected the action options, e.g. `use:tooltip={options}`",
+                codes::INVALID_ACTION,
+            ))
+        }
+    };
+    if action.sym.contains('-') {
+        return Err(SpanError::new(
+            action.span,
+            &format!("\"{}\" is not a valid action name", action.sym),
+            codes::INVALID_ACTION,
+        ));
+    }
+    // The action is called once, so it can't change
+    let scope = &builder.scope;
+    if scope.is_live(&action.sym) || scope.is_param(&action.sym) {
+        return Err(SpanError::new(
+            action.span,
+            &format!(
+                "Action \"{}\" cannot depend on parameters, pass them as the options instead",
+                action.sym
+            ),
+            codes::INVALID_ACTION,
+        ));
+    }
+    let ctx = &builder.ctx;
+    if ctx.mode() == OutputMode::Ssr {
+        return Ok(());
+    }
+    let action = Box::from(Expr::Ident(action));
+    let mut params = params;
+    let deps = match &mut params {
+        Some(params) => match tr_expr(ctx, params, &builder.scope)? {
+            TrValue::None => None,
+            TrValue::Deps(deps) => Some(deps),
+        },
+        None => None,
+    };
+    let expr = ctx.action(
+        node_name,
+        action,
+        params,
+        deps.as_ref(),
+        &builder.unmount_sig_name,
+    );
+    builder.push_body_expr(expr);
+    Ok(())
+}
+
+/// Class and style directives like `class:active={flag}` and `style:color={color}`,
+/// or their object forms like `class={{ active: flag }}` and `style={{ color }}`
+#[derive(Clone, Copy)]
+enum Directive {
+    Class,
+    Style,
+}
+
+impl Directive {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "class" => Some(Directive::Class),
+            "style" => Some(Directive::Style),
+            _ => None,
+        }
+    }
+
+    fn of(attr: &JSXAttr) -> Option<Self> {
+        match &attr.name {
+            JSXAttrName::JSXNamespacedName(nn) => Self::from_name(&nn.ns.sym),
+            JSXAttrName::Ident(ident) => match &attr.value {
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) if expr.is_object() => Self::from_name(&ident.sym),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Entries of the class and style directives rendered into single attributes
+/// merged with the plain ones (only for `OutputMode::Ssr`)
+#[derive(Default)]
+struct RenderedDirectives {
+    /// `[token, on]` pairs
+    classes: Vec<(Box<Expr>, Box<Expr>)>,
+    /// `[name, value]` pairs
+    styles: Vec<(Box<Expr>, Box<Expr>)>,
+}
+
+/// Binds every class token or style property separately, so each of them
+/// tracks its own dependencies and keeps the rest of the attribute intact.
+fn tr_el_directive(
+    attr: &mut JSXAttr,
+    directive: Directive,
+    builder: &mut ElBuilder,
+    node_name: &JsWord,
+) -> Result<(), SpanError> {
+    let mut exprs = vec![];
+    for (name, mut value) in directive_entries(attr)? {
+        let ctx = &builder.ctx;
+        let deps = match tr_expr(ctx, &mut value, &builder.scope)? {
+            TrValue::None => None,
+            TrValue::Deps(deps) => Some(deps),
+        };
+        if let Some(rendered) = &mut builder.rendered_directives {
+            match directive {
+                Directive::Class => rendered.classes.push((Box::from(&*name), value)),
+                Directive::Style => {
+                    let name = html::css_property_name(&name);
+                    rendered.styles.push((Box::from(&*name), value));
+                }
+            };
+            continue;
+        }
+        let sig = Some(&builder.unmount_sig_name);
+        match directive {
+            Directive::Class => {
+                for token in name.split_whitespace() {
+                    let value = value.clone();
+                    exprs.push(ctx.toggle_class(node_name, token, value, deps.as_ref(), sig));
+                }
+            }
+            Directive::Style => {
+                let name = html::css_property_name(&name);
+                exprs.push(ctx.style(node_name, &name, value, deps.as_ref(), sig));
+            }
+        };
+    }
+    for expr in exprs {
+        builder.push_body_expr(expr);
+    }
+    Ok(())
+}
+
+/// Names and values of the directive, which are the static entries of the object form
+fn directive_entries(attr: &mut JSXAttr) -> Result<Vec<(String, Box<Expr>)>, SpanError> {
+    let span = attr.span;
+    let value = match attr.value.take() {
+        None => Box::from(true),
+        Some(JSXAttrValue::Lit(lit)) => Box::from(lit),
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) => expr,
+        Some(JSXAttrValue::JSXExprContainer(_)) => return Ok(vec![]),
+        Some(_) => {
+            return Err(SpanError::new(
+                span,
+                "Expected an expression as the directive value",
+                codes::INVALID_DIRECTIVE,
+            ))
+        }
+    };
+    let obj = match (&attr.name, *value) {
+        (JSXAttrName::JSXNamespacedName(nn), value) => {
+            return Ok(vec![(nn.name.sym.to_string(), Box::from(value))]);
+        }
+        (JSXAttrName::Ident(_), Expr::Object(obj)) => obj,
+        (JSXAttrName::Ident(_), _) => return Ok(vec![]),
+    };
+    let err = |span: Span| {
+        SpanError::new(
+            span,
+            "Expected a static key, e.g. `{ active: flag }` or `{ \"font-size\": size }`",
+            codes::INVALID_DIRECTIVE,
+        )
+    };
+    obj.props
+        .into_iter()
+        .map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match *prop {
+                Prop::Shorthand(ident) => Ok((ident.sym.to_string(), Box::from(ident))),
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    value,
+                }) => Ok((key.sym.to_string(), value)),
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(key),
+                    value,
+                }) => Ok((key.value.to_string(), value)),
+                prop => Err(err(prop.span())),
+            },
+            PropOrSpread::Spread(spread) => Err(err(spread.expr.span())),
+        })
+        .collect()
+}
+
+/// `value`, `prop:value` or `bind:value` set to an expression
+fn is_value_attr(attr: &JSXAttr) -> bool {
+    let name = match &attr.name {
+        JSXAttrName::Ident(ident) => &ident.sym,
+        JSXAttrName::JSXNamespacedName(nn) if matches!(&*nn.ns.sym, "prop" | "bind") => {
+            &nn.name.sym
+        }
+        JSXAttrName::JSXNamespacedName(_) => return false,
+    };
+    &**name == "value" && has_expr_value(attr)
+}
+
+/// The attribute value is an expression, e.g. `onmyevent={handler}`
+fn has_expr_value(attr: &JSXAttr) -> bool {
+    matches!(
+        &attr.value,
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(_),
+            ..
+        }))
+    )
+}
+
+const EVENT_MODIFIERS: [&str; 5] = ["prevent", "stop", "once", "passive", "capture"];
+
+/// Splits `on:click_prevent_once` into the event and its modifiers. JSX names
+/// can't have `|`, so they're separated by `_` and only known ones are taken.
+fn split_event_modifiers(name: &str) -> (String, Vec<String>) {
+    let mut parts: Vec<&str> = name.split('_').collect();
+    let mut modifiers = vec![];
+    while parts.len() > 1 && parts.last().is_some_and(|m| EVENT_MODIFIERS.contains(m)) {
+        modifiers.extend(parts.pop().map(str::to_string));
+    }
+    modifiers.reverse();
+    (parts.join("_"), modifiers)
+}
+
+enum AttrKind {
+    Attr,
+    /// The event name and its modifiers
+    Event(String, Vec<String>),
+    /// Known properties like `value` or `checked` reflecting the live state of an element
+    Prop,
+    /// `prop:name`
+    ExplicitProp,
+}
+
 fn tr_el_spread_attr(
     attr: &mut SpreadElement,
     builder: &mut ElBuilder,
@@ -387,6 +1053,12 @@ fn tr_el_spread_attr(
 ) -> Result<(), SpanError> {
     let ctx = &builder.ctx;
     let expr = &mut attr.expr;
+    if ctx.mode() == OutputMode::Ssr {
+        tr_expr(ctx, expr, &builder.scope)?;
+        let expr = ctx.attrs_to_html(expr.clone());
+        builder.push_html_expr(*expr);
+        return Ok(());
+    }
     let expr = match tr_expr(ctx, expr, &builder.scope)? {
         TrValue::None => ctx.attrs(node_name, expr.clone(), None, None),
         TrValue::Deps(deps) => ctx.attrs(
@@ -426,7 +1098,7 @@ impl NodePath {
 
     fn to_expr(&self) -> Box<Expr> {
         match self {
-            NodePath::Root(n) => ident_expr(&n),
+            NodePath::Root(n) => ident_expr(n),
             NodePath::FirstOf(path) => member_expr(
                 path.to_expr(),
                 MemberProp::Ident(ident(&"firstChild".into())),
@@ -447,49 +1119,58 @@ fn str_from_nn(nn: &JSXNamespacedName) -> String {
 
 struct ElBuilder<'a> {
     ctx: TrContext,
+    span: Span,
     scope: Scope<'a>,
     container_name: JsWord,
     unmount_sig_name: JsWord,
-    html: Vec<Box<Expr>>,
+    html: Vec<Expr>,
     body: Vec<Stmt>,
     show_body: bool,
+    /// Rendered content of the current element (only for `OutputMode::Ssr`)
+    content: Option<Box<Expr>>,
+    /// The current element has class or style directives
+    has_directives: bool,
+    rendered_directives: Option<RenderedDirectives>,
 }
 
 impl<'a> ElBuilder<'a> {
-    fn new(ctx: &TrContext, scope: &'a Scope) -> Self {
+    fn new(ctx: &TrContext, scope: &'a Scope, span: Span) -> Self {
         const CONTAINER: &str = "container";
+        const NODE: &str = "node";
         const UNMOUNT_SIGNAL: &str = "unmountSignal";
 
         let mut scope = Scope::child_of(scope);
-        let container_name = scope.insert_str_prefixed(CONTAINER);
+        let container_name = scope.insert_str_prefixed(match ctx.mode() {
+            OutputMode::Hydrate => NODE,
+            OutputMode::Dom | OutputMode::Element | OutputMode::Ssr => CONTAINER,
+        });
         let unmount_sig_name = scope.insert_str_prefixed(UNMOUNT_SIGNAL);
-        return Self {
+        Self {
             ctx: ctx.nested(unmount_sig_name.clone()),
+            span,
             scope,
             container_name,
             unmount_sig_name,
             html: Default::default(),
             body: Default::default(),
             show_body: false,
-        };
+            content: None,
+            has_directives: false,
+            rendered_directives: None,
+        }
     }
 
-    fn push_html_expr(&mut self, expr: Box<Expr>) {
+    fn push_html_expr(&mut self, expr: Expr) {
         let last_str = self
             .html
             .last_mut()
-            .map(|expr| expr.as_mut_lit())
-            .flatten()
-            .map(|lit| if let Lit::Str(s) = lit { Some(s) } else { None })
-            .flatten();
-        let new_str = expr
-            .as_lit()
-            .map(|lit| match lit {
-                Lit::Str(s) => Some(s.value.to_string()),
-                Lit::JSXText(t) => Some(t.value.to_string()),
-                _ => None,
-            })
-            .flatten();
+            .and_then(|expr| expr.as_mut_lit())
+            .and_then(|lit| if let Lit::Str(s) = lit { Some(s) } else { None });
+        let new_str = expr.as_lit().and_then(|lit| match lit {
+            Lit::Str(s) => Some(s.value.to_string()),
+            Lit::JSXText(t) => Some(t.value.to_string()),
+            _ => None,
+        });
         match (last_str, new_str) {
             (Some(last_str), Some(new_str)) => {
                 let s = last_str.value.to_string() + new_str.as_str();
@@ -500,13 +1181,11 @@ impl<'a> ElBuilder<'a> {
     }
 
     fn push_html_str(&mut self, s: &str) {
-        self.push_html_expr(Box::from(s))
+        self.push_html_expr(Expr::from(s))
     }
 
-    fn push_html_attr(&mut self, name: &str, expr: Box<Expr>) {
-        self.push_html_str(&format!(" {name}=\""));
-        self.push_html_expr(expr);
-        self.push_html_str("\"");
+    fn push_html_attr(&mut self, name: &str, value: &str) {
+        self.push_html_str(&format!(" {name}=\"{}\"", html::escape_attr(value)));
     }
 
     fn push_body_expr(&mut self, expr: Box<Expr>) {
@@ -520,9 +1199,25 @@ impl<'a> ElBuilder<'a> {
         container_name: &JsWord,
         node_path: &NodePath,
     ) -> NodePath {
+        if self.ctx.mode() == OutputMode::Ssr {
+            // Hydration looks for the markers to replace the content
+            let expr = self.ctx.to_html(expr);
+            self.push_html_str("<!--[-->");
+            self.push_html_expr(*expr);
+            self.push_html_str("<!--]-->");
+            return node_path.clone();
+        }
         self.show_body = true;
         self.push_html_str("<!>");
-        let anchor = self.push_node_path("anchor", node_path);
+        let anchor = match self.ctx.mode() {
+            OutputMode::Hydrate => {
+                let node = self.ctx.anchor(node_path.to_expr());
+                self.push_node_expr("anchor", node)
+            }
+            OutputMode::Dom | OutputMode::Element | OutputMode::Ssr => {
+                self.push_node_path("anchor", node_path)
+            }
+        };
         self.body
             .push(stmt_from_expr(self.ctx.unmount_on(self.ctx.insert(
                 expr,
@@ -533,55 +1228,69 @@ impl<'a> ElBuilder<'a> {
     }
 
     fn push_node_path(&mut self, prefix: &str, node_path: &NodePath) -> NodePath {
+        self.push_node_expr(prefix, node_path.to_expr())
+    }
+
+    fn push_node_expr(&mut self, prefix: &str, node: Box<Expr>) -> NodePath {
         let name = self.scope.insert_prefixed_item_with_offset(
             &format!("{prefix}__", prefix = prefix.replace(":", "_")).into(),
             Some(1),
             ScopeItem::Default,
         );
-        self.body.push(const_decl(&name, node_path.to_expr()));
+        self.body.push(const_decl(&name, node));
         NodePath::Root(name)
     }
 
     fn build(self) -> Box<Expr> {
-        self.ctx.element(
-            {
-                let mut iter = self.html.into_iter();
-                if let Some(first_expr) = iter.next() {
-                    let mut expr = first_expr;
-                    for e in iter {
-                        let e = paren_expr(e);
-                        let bin = BinExpr {
-                            span: DUMMY_SP,
-                            op: op!(bin, "+"),
-                            left: expr,
-                            right: e,
-                        };
-                        expr = Box::new(Expr::Bin(bin));
-                    }
-                    Some(expr)
-                } else {
-                    None
-                }
-            },
-            {
-                if self.show_body {
-                    Some(arrow_expr(
-                        Some(vec![
-                            ident_pat(&self.container_name),
-                            ident_pat(&self.unmount_sig_name),
-                        ]),
-                        block_or_expr_from_stmts(self.body),
-                    ))
-                } else {
-                    None
+        let html = {
+            let mut iter = self.html.into_iter();
+            if let Some(first_expr) = iter.next() {
+                let mut expr = Box::new(first_expr);
+                for e in iter {
+                    let e = match e {
+                        Expr::Lit(_) | Expr::Call(_) => Box::new(e),
+                        _ => paren_expr(Box::new(e)),
+                    };
+                    let bin = BinExpr {
+                        span: DUMMY_SP,
+                        op: op!(bin, "+"),
+                        left: expr,
+                        right: e,
+                    };
+                    expr = Box::new(Expr::Bin(bin));
                 }
-            },
-        )
+                Some(expr)
+            } else {
+                None
+            }
+        };
+        if self.ctx.mode() == OutputMode::Ssr {
+            return self
+                .ctx
+                .html(self.span, html.unwrap_or_else(|| Box::from("")));
+        }
+        let build = match self.ctx.mode() {
+            OutputMode::Hydrate => TrContext::adopt,
+            OutputMode::Dom | OutputMode::Element | OutputMode::Ssr => TrContext::element,
+        };
+        build(&self.ctx, self.span, html, {
+            if self.show_body {
+                Some(arrow_expr(
+                    Some(vec![
+                        ident_pat(&self.container_name),
+                        ident_pat(&self.unmount_sig_name),
+                    ]),
+                    block_or_expr_from_stmts(self.body),
+                ))
+            } else {
+                None
+            }
+        })
     }
 }
 
 enum ElName {
-    HTML(String),
+    Html(String),
     Custom(Box<Expr>),
 }
 
@@ -593,7 +1302,7 @@ impl From<&JSXElementName> for ElName {
                 if s.starts_with(char::is_uppercase) {
                     Self::Custom(ident_expr(&s.into()))
                 } else {
-                    Self::HTML(s)
+                    Self::Html(s)
                 }
             }
             JSXElementName::JSXMemberExpr(expr) => {
@@ -606,14 +1315,14 @@ impl From<&JSXElementName> for ElName {
                 }
                 Self::Custom(to_member_expr(expr))
             }
-            JSXElementName::JSXNamespacedName(nn) => Self::HTML(str_from_nn(nn)),
+            JSXElementName::JSXNamespacedName(nn) => Self::Html(str_from_nn(nn)),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::jsx::tr_child_text;
+    use crate::jsx::{split_event_modifiers, tr_child_text};
 
     #[test]
     fn test_child_text() {
@@ -621,8 +1330,8 @@ mod tests {
         assert_eq!(tr_child_text("     text"), "     text");
         assert_eq!(tr_child_text("text "), "text ");
         assert_eq!(tr_child_text("text     "), "text     ");
-        assert_eq!(tr_child_text("\u{a0}text"), "&nbsp;text");
-        assert_eq!(tr_child_text("text\u{a0}"), "text&nbsp;");
+        assert_eq!(tr_child_text("\u{a0}text"), "\u{a0}text");
+        assert_eq!(tr_child_text("text\u{a0}"), "text\u{a0}");
         assert_eq!(tr_child_text("\n\n            "), "");
         assert_eq!(tr_child_text("\ntext\n"), "text");
         assert_eq!(
@@ -631,7 +1340,29 @@ mod tests {
         );
         assert_eq!(
             tr_child_text("\n   \u{a0}   \u{a0}\n    "),
-            "&nbsp;   &nbsp;"
+            "\u{a0}   \u{a0}"
+        );
+    }
+
+    #[test]
+    fn test_split_event_modifiers() {
+        let split = |name| {
+            let (event, modifiers) = split_event_modifiers(name);
+            (event, modifiers.join("|"))
+        };
+        assert_eq!(split("click"), ("click".to_string(), "".to_string()));
+        assert_eq!(
+            split("click_prevent_once"),
+            ("click".to_string(), "prevent|once".to_string())
+        );
+        assert_eq!(
+            split("my-event_stop"),
+            ("my-event".to_string(), "stop".to_string())
+        );
+        assert_eq!(
+            split("my_event_capture"),
+            ("my_event".to_string(), "capture".to_string())
         );
+        assert_eq!(split("prevent"), ("prevent".to_string(), "".to_string()));
     }
 }
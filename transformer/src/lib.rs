@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, path::Path};
 
 use serde::{
     de::{value::Error as DeserializeError, IntoDeserializer},
@@ -6,35 +6,57 @@ use serde::{
 };
 use swc_core::{
     common::{
-        errors::{self as swc_errors},
+        comments::SingleThreadedComments,
+        errors::{self as swc_errors, DiagnosticId},
         sync::{Lazy, Lrc},
         util::take::Take,
-        FileName, SourceFile, SourceMap, DUMMY_SP,
+        BytePos, FileName, SourceFile, SourceMap, Span, DUMMY_SP,
     },
     ecma::{
         ast::*,
+        atoms::JsWord,
         codegen,
         codegen::Emitter,
         parser::{self, parse_file_as_module, EsConfig, TsConfig},
         visit::*,
     },
+    testing,
 };
 
+pub use errors::{Diagnostic, Diagnostics, Position, Range, Severity};
+pub use import::ImportStyle;
+pub use mode::OutputMode;
 use scope::*;
+pub use srcmap::SourceMapKind;
 pub use syntax::Syntax;
 use tr::*;
+pub use views::NamedViews;
 
-use self::{context::TrContext, utils::*};
+use self::{
+    context::TrContext,
+    elements::ElementDef,
+    errors::{codes, Collector},
+    utils::*,
+};
 
+mod commonjs;
 mod context;
+mod elements;
 mod errors;
 mod glob;
+mod html;
+mod import;
 mod jsx;
 mod live;
+mod mode;
 mod scope;
+mod srcmap;
+mod state;
 mod syntax;
 mod tr;
+mod types;
 mod utils;
+mod views;
 
 struct EsMappedVersion {
     ver: EsVersion,
@@ -63,10 +85,19 @@ pub static ES_SUPPORTED_VERSIONS: Lazy<Vec<&str>> = Lazy::new(|| {
 
 pub const ES_DEFAULT_VERSION: &str = "es6";
 
+/// The transformer version, e.g. to invalidate cached outputs
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub struct Options {
     pub syntax: Syntax,
     pub target: EsVersion,
     pub can_emit_warnings: bool,
+    pub source_map: Option<SourceMapKind>,
+    pub named_views: NamedViews,
+    /// The runtime module specifier, "viewmill-runtime" by default
+    pub runtime: String,
+    pub import_style: ImportStyle,
+    pub mode: OutputMode,
 }
 
 impl Options {
@@ -79,6 +110,11 @@ impl Options {
             syntax,
             target: EsMappedVersion::parse(target.unwrap_or(ES_DEFAULT_VERSION))?.ver,
             can_emit_warnings: can_emit_warnings.unwrap_or(false),
+            source_map: None,
+            named_views: NamedViews::default(),
+            runtime: import::DEFAULT_RUNTIME.to_string(),
+            import_style: ImportStyle::default(),
+            mode: OutputMode::default(),
         })
     }
 }
@@ -86,33 +122,110 @@ impl Options {
 pub struct Output {
     pub src: String,
     pub ext: &'static str,
+    /// The v3 source map json if `SourceMapKind::Separate` is requested.
+    pub map: Option<String>,
+    /// Warnings emitted during the transformation.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+pub fn tr_str(input: &str, options: Options) -> Result<Output, Diagnostics> {
+    tr_file_name_str(FileName::Anon, input, options)
 }
 
-pub fn tr_str(input: &str, options: Options) -> Result<Output, Box<dyn Error>> {
+/// The same as `tr_str`, but the name is used as a source in source maps and diagnostics.
+pub fn tr_named_str(name: &str, input: &str, options: Options) -> Result<Output, Diagnostics> {
+    tr_file_name_str(FileName::Custom(name.to_string()), input, options)
+}
+
+fn tr_file_name_str(
+    file_name: FileName,
+    input: &str,
+    options: Options,
+) -> Result<Output, Diagnostics> {
     let cm: Lrc<SourceMap> = Default::default();
-    let handler = swc_errors::Handler::with_tty_emitter(
-        swc_errors::ColorConfig::Auto,
-        options.can_emit_warnings,
-        false,
-        Some(cm.clone()),
-    );
-    swc_errors::HANDLER.set(&handler, || {
-        let fm = cm.new_source_file(FileName::Anon, input.into());
-        tr_file(&fm, cm, options, true)
-    })
+    let fm = cm.new_source_file(file_name, input.into());
+    tr_file(&fm, cm, options, true)
 }
 
+/// Transforms the file collecting every error and warning instead of
+/// stopping at the first one.
 pub fn tr_file(
     fm: &SourceFile,
     cm: Lrc<SourceMap>,
     options: Options,
     show_header: bool,
+) -> Result<Output, Diagnostics> {
+    let (collector, collected) = Collector::new(cm.clone());
+    let handler =
+        swc_errors::Handler::with_emitter(options.can_emit_warnings, false, Box::new(collector));
+    let result = swc_errors::HANDLER.set(&handler, || tr_module(fm, cm, options, show_header));
+    let mut diagnostics: Vec<Diagnostic> = collected.lock().drain(..).collect();
+    match result {
+        Ok(mut output) if !handler.has_errors() => {
+            output.diagnostics = diagnostics;
+            Ok(output)
+        }
+        Ok(_) => Err(Diagnostics(diagnostics)),
+        Err(e) => {
+            if !handler.has_errors() {
+                diagnostics.push(Diagnostic::internal(e));
+            }
+            Err(Diagnostics(diagnostics))
+        }
+    }
+}
+
+/// The transformed file compared with its existing output.
+pub struct Check {
+    pub output: Output,
+    /// The difference between the existing and the transformed sources, `None` if they're equal.
+    pub diff: Option<String>,
+}
+
+impl Check {
+    pub fn is_up_to_date(&self) -> bool {
+        self.diff.is_none()
+    }
+}
+
+/// Transforms the file and compares the result with the existing output,
+/// e.g. to verify generated files are committed up to date.
+pub fn check_file(
+    fm: &SourceFile,
+    cm: Lrc<SourceMap>,
+    options: Options,
+    show_header: bool,
+    existing: &str,
+) -> Result<Check, Diagnostics> {
+    let output = tr_file(fm, cm, options, show_header)?;
+    let diff = (output.src != existing).then(|| testing::diff(existing, &output.src));
+    Ok(Check { output, diff })
+}
+
+/// The same as `check_file`, but for a named string like `tr_named_str`.
+pub fn check_named_str(
+    name: &str,
+    input: &str,
+    options: Options,
+    existing: &str,
+) -> Result<Check, Diagnostics> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Custom(name.to_string()), input.into());
+    check_file(&fm, cm, options, true, existing)
+}
+
+fn tr_module(
+    fm: &SourceFile,
+    cm: Lrc<SourceMap>,
+    options: Options,
+    show_header: bool,
 ) -> Result<Output, Box<dyn Error>> {
     let target = options.target;
+    let comments = SingleThreadedComments::default();
     let module = {
         let mut recovered_errors = vec![];
         let result = parse_file_as_module(
-            &fm,
+            fm,
             {
                 use parser::Syntax::{Es, Typescript};
                 match options.syntax {
@@ -127,25 +240,51 @@ pub fn tr_file(
                 }
             },
             target,
-            None,
+            Some(&comments),
             &mut recovered_errors,
         );
         swc_errors::HANDLER.with(|handler| {
-            for e in recovered_errors {
-                e.into_diagnostic(&handler).emit();
-            }
-        });
-        result.map_err(|e| Box::<dyn Error>::from(e.kind().msg()))
+            let syntax_err = |e: parser::error::Error| {
+                e.into_diagnostic(handler)
+                    .code(DiagnosticId::Error(codes::SYNTAX.to_string()))
+                    .emit();
+            };
+            recovered_errors.into_iter().for_each(syntax_err);
+            result.map_err(|e| {
+                let msg = e.kind().msg().to_string();
+                syntax_err(e);
+                Box::<dyn Error>::from(msg)
+            })
+        })
     }?;
     let mut root_scope = Scope::from(&module);
-    let module = Program::Module(module)
+    live::insert_module_lives(&module, &comments, &mut root_scope);
+    state::insert_runtime_imports(&module, &options.runtime, &mut root_scope);
+    views::insert_named_views(&module, &comments, options.named_views, &mut root_scope);
+    let mut module = Program::Module(module)
         .fold_with({
-            let tr_ctx = TrContext::new(&fm.src, &mut root_scope);
-            &mut as_folder(Transformer { root_scope, tr_ctx })
+            let tr_ctx = TrContext::new(&fm.src, &comments, &mut root_scope, &options);
+            &mut as_folder(Transformer {
+                root_scope,
+                tr_ctx,
+                comments: &comments,
+                file_stem: match &fm.name {
+                    FileName::Real(path) => path.file_stem().map(|s| s.to_string_lossy().into()),
+                    FileName::Custom(name) => Path::new(name)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into()),
+                    _ => None,
+                },
+                elements: vec![],
+            })
         })
         .module()
-        .ok_or_else(|| "Transformation failed")?;
+        .ok_or("Transformation failed")?;
+    if options.import_style == ImportStyle::Require && options.syntax == Syntax::Js {
+        commonjs::to_commonjs(&mut module, &fm.src);
+    }
     let mut src = Vec::new();
+    let mut mappings = vec![];
     let mut emitter = {
         Emitter {
             cfg: codegen::Config {
@@ -154,97 +293,256 @@ pub fn tr_file(
             },
             cm: cm.clone(),
             comments: None,
-            wr: codegen::text_writer::JsWriter::new(cm.clone(), "\n", &mut src, None),
+            wr: codegen::text_writer::JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut src,
+                options.source_map.map(|_| &mut mappings),
+            ),
         }
     };
     emitter.emit_module(&module)?;
     let mut src = String::from_utf8(src)?;
+    let mut line_offset = 0;
     if show_header {
         src = format!("{FILE_HEADER}\n{src}");
+        line_offset = FILE_HEADER.lines().count() as u32;
     }
+    let map = match options.source_map {
+        Some(kind) => {
+            let json = srcmap::build(&cm, mappings, line_offset)?;
+            match kind {
+                SourceMapKind::Inline => {
+                    if !src.ends_with('\n') {
+                        src.push('\n');
+                    }
+                    src.push_str(&srcmap::inline_comment(&json));
+                    src.push('\n');
+                    None
+                }
+                SourceMapKind::Separate => Some(json),
+            }
+        }
+        None => None,
+    };
     Ok(Output {
         src,
         ext: options.syntax.ext(),
+        map,
+        diagnostics: vec![],
     })
 }
 
 struct Transformer<'a> {
     root_scope: Scope<'a>,
     tr_ctx: TrContext,
+    comments: &'a SingleThreadedComments,
+    /// Names the default view as a custom element
+    file_stem: Option<String>,
+    /// Views to define as custom elements (only for `OutputMode::Element`)
+    elements: Vec<ElementDef>,
+}
+
+impl Transformer<'_> {
+    /// Rendered views get plain values, so their params aren't live
+    fn param_item(&self) -> ScopeItem {
+        match self.tr_ctx.mode() {
+            OutputMode::Dom | OutputMode::Hydrate | OutputMode::Element => ScopeItem::Param,
+            OutputMode::Ssr => ScopeItem::RenderedParam,
+        }
+    }
+
+    fn define_element(
+        &mut self,
+        positions: &[BytePos],
+        view_name: Option<&str>,
+        view: Option<JsWord>,
+        func: &Function,
+    ) {
+        if self.tr_ctx.mode() != OutputMode::Element {
+            return;
+        }
+        // The default view is named after the file
+        let view_name = view_name.or(self.file_stem.as_deref());
+        match ElementDef::new(self.comments, positions, view_name, view, func) {
+            Ok(def) => self.elements.push(def),
+            Err(err) => err.emit(),
+        }
+    }
+
+    /// Names the anonymous default view and defines all the views as custom elements
+    fn insert_elements(&mut self, module: &mut Module) {
+        for item in module.body.iter_mut() {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) = item else {
+                continue;
+            };
+            let Expr::Fn(expr) = &mut *export.expr else {
+                continue;
+            };
+            let Some(def) = self.elements.iter_mut().find(|def| def.view.is_none()) else {
+                continue;
+            };
+            let name = glob::uname("View", self.tr_ctx.src());
+            def.view = Some(name.clone());
+            *item = ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                span: export.span,
+                decl: DefaultDecl::Fn(FnExpr {
+                    ident: Some(ident(&name)),
+                    function: expr.function.take(),
+                }),
+            }));
+        }
+        for def in self.elements.iter() {
+            let Some(view) = &def.view else {
+                continue;
+            };
+            let call = self
+                .tr_ctx
+                .define_element(&def.tag_name, view, &def.params, def.shadow);
+            module.body.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: call,
+            })));
+        }
+    }
+
+    fn tr_arrow_view(&self, arrow: ArrowExpr) -> Box<Function> {
+        let mut arrow = arrow;
+        let mut scope = Scope::child_of(&self.root_scope);
+        let param_item = self.param_item();
+        let param_len = arrow.params.len();
+        let mut args = Vec::with_capacity(param_len);
+        let mut model = Vec::with_capacity(param_len);
+        for p in arrow.params.into_iter() {
+            walk_every_pat_idents(&p, |ident| {
+                scope.insert_item(&ident.sym, param_item);
+                model.push(ident.clone());
+            });
+            scope.insert_pat_types(&p);
+            args.push(Param::from(p));
+        }
+        if let Err(err) = tr_view_body(&self.tr_ctx, &mut arrow.body, &scope) {
+            err.emit();
+        }
+        view_func(
+            &self.tr_ctx,
+            arrow.span,
+            args,
+            model,
+            arrow.body,
+            arrow.type_params,
+        )
+    }
+
+    fn tr_fn_view(&self, func: Box<Function>) -> Box<Function> {
+        let mut func = func;
+        let mut scope = Scope::child_of(&self.root_scope);
+        let param_item = self.param_item();
+        let mut model = Vec::with_capacity(func.params.len());
+        for p in func.params.iter() {
+            walk_every_pat_idents(&p.pat, |ident| {
+                scope.insert_item(&ident.sym, param_item);
+                model.push(ident.clone());
+            });
+            scope.insert_pat_types(&p.pat);
+        }
+        let Some(body) = &mut func.body else {
+            return func;
+        };
+        let mut body = body.take();
+        if let Err(err) = tr_block(&self.tr_ctx, &mut body, &scope, true) {
+            err.emit();
+        }
+        view_func(
+            &self.tr_ctx,
+            func.span,
+            func.params,
+            model,
+            Box::new(BlockStmtOrExpr::BlockStmt(body)),
+            func.type_params,
+        )
+    }
 }
 
 impl VisitMut for Transformer<'_> {
     fn visit_mut_export_default_expr(&mut self, n: &mut ExportDefaultExpr) {
         n.visit_mut_children_with(self);
-        swc_errors::HANDLER.with(|handler| {
-            if let Expr::Arrow(arrow) = &mut *n.expr {
-                let mut arrow = arrow.take();
-                let mut scope = Scope::child_of(&self.root_scope);
-                let param_len = arrow.params.len();
-                let mut args = Vec::with_capacity(param_len);
-                let mut model = Vec::with_capacity(param_len);
-                for p in arrow.params.into_iter() {
-                    walk_every_pat_idents(&p, |ident| {
-                        scope.insert_item(&ident.sym, ScopeItem::Live);
-                        model.push(ident.clone());
-                    });
-                    args.push(Param::from(p));
-                }
-                if let Err(err) = tr_block_or_expr(&self.tr_ctx, &mut arrow.body, &scope) {
-                    handler.span_err(err.span, &err.msg);
-                }
-                n.expr = Box::from(view_func(
-                    &self.tr_ctx,
-                    args,
-                    model,
-                    arrow.body,
-                    arrow.type_params,
-                ));
-            }
-        });
+        if let Expr::Arrow(arrow) = &mut *n.expr {
+            let func = self.tr_arrow_view(arrow.take());
+            // Named later to be referred by the element definition
+            self.define_element(&[n.span.lo], None, None, &func);
+            n.expr = Box::from(FnExpr {
+                ident: None,
+                function: func,
+            });
+        }
     }
 
     fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
         n.visit_mut_children_with(self);
-        swc_errors::HANDLER.with(|handler| {
-            if let DefaultDecl::Fn(decl) = &mut n.decl {
-                let mut func = decl.function.take();
-                let mut scope = Scope::child_of(&self.root_scope);
-                let mut model = Vec::with_capacity(func.params.len());
-                for p in func.params.iter() {
-                    walk_every_pat_idents(&p.pat, |ident| {
-                        scope.insert_item(&ident.sym, ScopeItem::Live);
-                        model.push(ident.clone());
-                    });
-                }
-                if let Some(body) = &mut func.body {
-                    let mut body = body.take();
-                    if let Err(err) = tr_block(&self.tr_ctx, &mut body, &scope) {
-                        handler.span_err(err.span, &err.msg);
+        if let DefaultDecl::Fn(decl) = &mut n.decl {
+            decl.function = self.tr_fn_view(decl.function.take());
+            if self.tr_ctx.mode() == OutputMode::Element {
+                let ident = decl
+                    .ident
+                    .get_or_insert_with(|| ident(&glob::uname("View", self.tr_ctx.src())));
+                let view = ident.sym.clone();
+                self.define_element(&[n.span.lo], None, Some(view), &decl.function);
+            }
+        }
+    }
+
+    fn visit_mut_export_decl(&mut self, n: &mut ExportDecl) {
+        n.visit_mut_children_with(self);
+        // Named views are found by `views::insert_named_views`
+        match &mut n.decl {
+            Decl::Fn(decl) if self.root_scope.is_view(&decl.ident.sym) => {
+                decl.function = self.tr_fn_view(decl.function.take());
+                let name = decl.ident.sym.clone();
+                let positions = [n.span.lo, decl.function.span.lo];
+                self.define_element(&positions, Some(&name), Some(name.clone()), &decl.function);
+            }
+            Decl::Var(var) => {
+                for decl in var.decls.iter_mut() {
+                    let (Pat::Ident(name), Some(init)) = (&decl.name, &mut decl.init) else {
+                        continue;
+                    };
+                    if !self.root_scope.is_view(&name.id.sym) {
+                        continue;
+                    }
+                    let positions = [n.span.lo, var.span.lo];
+                    let name = &name.id.sym;
+                    match &mut **init {
+                        Expr::Arrow(arrow) => {
+                            let func = self.tr_arrow_view(arrow.take());
+                            self.define_element(&positions, Some(name), Some(name.clone()), &func);
+                            *init = Box::from(func);
+                        }
+                        Expr::Fn(expr) => {
+                            expr.function = self.tr_fn_view(expr.function.take());
+                            let func = &expr.function;
+                            self.define_element(&positions, Some(name), Some(name.clone()), func);
+                        }
+                        _ => (),
                     }
-                    decl.function = view_func(
-                        &self.tr_ctx,
-                        func.params,
-                        model,
-                        Box::new(BlockStmtOrExpr::BlockStmt(body)),
-                        func.type_params,
-                    );
                 }
             }
-        });
+            _ => (),
+        }
     }
 
     fn visit_mut_module(&mut self, n: &mut Module) {
-        n.body.insert(
-            0,
-            ModuleItem::ModuleDecl(ModuleDecl::Import(self.tr_ctx.import_decl())),
-        );
         n.visit_mut_children_with(self);
+        if self.tr_ctx.mode() == OutputMode::Element {
+            self.insert_elements(n);
+        }
+        n.body.insert(0, self.tr_ctx.import_item());
     }
 }
 
 fn view_func(
     ctx: &TrContext,
+    span: Span,
     args: Vec<Param>,
     model: Vec<Ident>,
     body: Box<BlockStmtOrExpr>,
@@ -253,13 +551,18 @@ fn view_func(
     Box::new(Function {
         params: args,
         decorators: Take::dummy(),
-        span: DUMMY_SP,
+        span,
         body: {
             Some(BlockStmt {
                 span: DUMMY_SP,
                 stmts: vec![Stmt::Return(ReturnStmt {
                     span: DUMMY_SP,
-                    arg: Some(ctx.view(model, body)),
+                    arg: Some(match ctx.mode() {
+                        OutputMode::Dom | OutputMode::Hydrate | OutputMode::Element => {
+                            ctx.view(model, body)
+                        }
+                        OutputMode::Ssr => ctx.server_view(model, body),
+                    }),
                 })],
             })
         },
@@ -270,6 +573,13 @@ fn view_func(
     })
 }
 
+static FILE_HEADER: &str = concat!(
+    "// DO NOT EDIT! This file is generated by vewmill.",
+    "\n// See https://github.com/apleshkov/viewmill for the details.",
+    "\n/* eslint-disable */",
+    "\n// @ts-nocheck"
+);
+
 #[cfg(test)]
 mod tests {
 
@@ -296,11 +606,16 @@ mod tests {
             EsVersion::Es2016
         );
     }
-}
 
-static FILE_HEADER: &str = concat!(
-    "// DO NOT EDIT! This file is generated by vewmill.",
-    "\n// See https://github.com/apleshkov/viewmill for the details.",
-    "\n/* eslint-disable */",
-    "\n// @ts-nocheck"
-);
+    #[test]
+    fn test_check() {
+        let input = "export default (a) => <p>{a}</p>;";
+        let options = || Options::try_new(Syntax::Js, None, None).unwrap();
+        let existing = tr_named_str("input.jsx", input, options()).unwrap().src;
+        let check = check_named_str("input.jsx", input, options(), &existing).unwrap();
+        assert!(check.is_up_to_date());
+        let check = check_named_str("input.jsx", input, options(), "lorem ipsum").unwrap();
+        assert!(!check.is_up_to_date());
+        assert!(check_named_str("input.jsx", "<", options(), &existing).is_err());
+    }
+}
@@ -1,37 +1,221 @@
+use std::collections::BTreeMap;
+
 use swc_core::{
-    common::DUMMY_SP,
+    common::{comments::SingleThreadedComments, BytePos, Span, Spanned, DUMMY_SP},
     ecma::{ast::*, atoms::JsWord},
 };
 
-use super::{context::TrContext, utils::*};
+use super::{
+    context::TrContext,
+    errors::{codes, SpanError},
+    scope::{Scope, ScopeItem},
+    utils::*,
+};
+
+const MARKER: &str = "@live";
+
+const EQ_PRAGMA: &str = "@viewmill-eq";
+
+fn is_marked(comments: &SingleThreadedComments, pos: BytePos) -> bool {
+    comments.with_leading(pos, |list| list.iter().any(|c| c.text.trim() == MARKER))
+}
+
+/// Inner comments like `{ /* @live */ foo }` are either leading or trailing ones
+/// depending on the surrounding tokens, so any marker in the range counts.
+fn is_marked_within(comments: &SingleThreadedComments, lo: BytePos, hi: BytePos) -> bool {
+    let (leading, trailing) = comments.borrow_all();
+    leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .any(|c| c.span.lo >= lo && c.span.hi <= hi && c.text.trim() == MARKER)
+}
 
-pub fn value_of(name: &JsWord) -> Box<Expr> {
-    obj_method_call(ident_expr(name), &"getValue".into(), None)
+/// Checks for `Live<T>` or `Param<T>` including qualified ones like `viewmill.Live<T>`
+fn is_live_type(ann: &Option<Box<TsTypeAnn>>) -> bool {
+    let Some(TsType::TsTypeRef(ty)) = ann.as_ref().map(|ann| &*ann.type_ann) else {
+        return false;
+    };
+    let name = match &ty.type_name {
+        TsEntityName::Ident(ident) => &ident.sym,
+        TsEntityName::TsQualifiedName(q) => &q.right.sym,
+    };
+    matches!(&**name, "Live" | "Param")
+}
+
+/// Inserts module-level and imported live values, so views depend on them.
+/// They're either marked by the `/* @live */` comment or typed as `Live<T>`.
+pub fn insert_module_lives(module: &Module, comments: &SingleThreadedComments, scope: &mut Scope) {
+    let marked = |pos| is_marked(comments, pos);
+    let marked_within = |lo, hi| is_marked_within(comments, lo, hi);
+    let insert_var = |var: &VarDecl, all: bool, scope: &mut Scope| {
+        let all = all || marked(var.span.lo);
+        let mut lo = var.span.lo;
+        for decl in var.decls.iter() {
+            let typed = match &decl.name {
+                Pat::Ident(binding) => is_live_type(&binding.type_ann),
+                _ => false,
+            };
+            if all || typed || marked_within(lo, decl.span.lo) {
+                scope.insert_pat_item(&decl.name, ScopeItem::Live);
+            }
+            lo = decl.span.hi;
+        }
+    };
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) if !decl.type_only => {
+                let all = marked(decl.span.lo);
+                let mut lo = decl.span.lo;
+                for s in decl.specifiers.iter() {
+                    let (s_lo, hi) = (lo, s.span().lo);
+                    lo = s.span().hi;
+                    let local = match s {
+                        ImportSpecifier::Named(s) if s.is_type_only => continue,
+                        ImportSpecifier::Named(s) => &s.local,
+                        ImportSpecifier::Default(s) => &s.local,
+                        ImportSpecifier::Namespace(_) => continue,
+                    };
+                    if all || marked_within(s_lo, hi) {
+                        scope.insert_item(&local.sym, ScopeItem::Live);
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Var(var),
+            })) => insert_var(var, marked(span.lo), scope),
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => insert_var(var, false, scope),
+            _ => (),
+        }
+    }
+}
+
+pub fn value_of(name: &JsWord, span: Span) -> Box<Expr> {
+    spanned_obj_method_call(
+        span,
+        Box::new(Expr::Ident(Ident::new(name.clone(), span))),
+        &"getValue".into(),
+        None,
+    )
+}
+
+pub fn set_value(name: &JsWord, value: Box<Expr>) -> Box<Expr> {
+    spanned_obj_method_call(
+        DUMMY_SP,
+        ident_expr(name),
+        &"setValue".into(),
+        Some(ArgsBuilder::from(value).build()),
+    )
 }
 
 pub fn var_initializer(
     ctx: &TrContext,
     pat: &mut Pat,
     expr: Box<Expr>,
-    deps: &Vec<JsWord>,
+    deps: &[Dep],
+    eq: Option<&Equality>,
 ) -> Option<Box<Expr>> {
     match pat {
-        Pat::Ident(_) => Some(ctx.live(expr, &deps, None)),
+        Pat::Ident(_) => Some(ctx.live(expr, deps, None, eq)),
         Pat::Array(arr) => {
             let d = DestructArg::from(&*arr);
             *pat = d.to_decl_pat();
-            Some(ctx.live(expr, &deps, Some(&d)))
+            Some(ctx.live(expr, deps, Some(&d), eq))
         }
         Pat::Object(obj) => {
             let d = DestructArg::from(&*obj);
             *pat = d.to_decl_pat();
-            Some(ctx.live(expr, &deps, Some(&d)))
+            Some(ctx.live(expr, deps, Some(&d), eq))
         }
         _ => None,
     }
 }
 
-pub fn deps_expr(deps: &Vec<JsWord>) -> Box<Expr> {
+/// How a live value compares its new value with the current one, declared by
+/// the `// @viewmill-eq shallow` or `// @viewmill-eq myEqualFn` comment.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Equality {
+    /// The runtime `shallowEqual` comparing arrays & plain objects by items
+    Shallow,
+    /// A function in scope taking the current and the new values
+    Custom(JsWord),
+}
+
+impl Equality {
+    fn parse(text: &str) -> Option<Result<Self, ()>> {
+        let value = text.trim().strip_prefix(EQ_PRAGMA)?;
+        if !value.is_empty() && !value.starts_with(char::is_whitespace) {
+            return None;
+        }
+        Some(match value.trim() {
+            "shallow" => Ok(Self::Shallow),
+            name if is_fn_name(name) => Ok(Self::Custom(name.into())),
+            _ => Err(()),
+        })
+    }
+}
+
+fn is_fn_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Collects the equality pragmas by the positions they lead, reporting
+/// the invalid ones.
+pub fn equality_pragmas(comments: &SingleThreadedComments) -> BTreeMap<BytePos, Equality> {
+    let (leading, _) = comments.borrow_all();
+    // Sorted to report errors in order
+    let mut leading: Vec<_> = leading.iter().collect();
+    leading.sort_by_key(|(pos, _)| **pos);
+    let mut result = BTreeMap::new();
+    for (pos, list) in leading {
+        for c in list.iter() {
+            match Equality::parse(&c.text) {
+                Some(Ok(eq)) => {
+                    result.insert(*pos, eq);
+                }
+                Some(Err(())) => SpanError::new(
+                    c.span,
+                    "Expected `shallow` or a function name after `@viewmill-eq`",
+                    codes::INVALID_PRAGMA,
+                )
+                .emit(),
+                None => (),
+            }
+        }
+    }
+    result
+}
+
+/// A live value the expression depends on, or only its member like `user.name`,
+/// so changes of other members are ignored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Dep {
+    pub name: JsWord,
+    pub path: Vec<JsWord>,
+}
+
+impl From<&JsWord> for Dep {
+    fn from(name: &JsWord) -> Self {
+        Self {
+            name: name.clone(),
+            path: vec![],
+        }
+    }
+}
+
+impl Dep {
+    /// Checks if every change of the other dependency is a change of this one
+    pub fn covers(&self, other: &Dep) -> bool {
+        self.name == other.name && other.path.starts_with(&self.path)
+    }
+}
+
+pub fn deps_expr(ctx: &TrContext, deps: &[Dep]) -> Box<Expr> {
     if deps.is_empty() {
         null_expr()
     } else {
@@ -39,9 +223,13 @@ pub fn deps_expr(deps: &Vec<JsWord>) -> Box<Expr> {
             .iter()
             .map(|d| ExprOrSpread {
                 spread: None,
-                expr: ident_expr(d),
+                expr: if d.path.is_empty() {
+                    ident_expr(&d.name)
+                } else {
+                    ctx.member(&d.name, &d.path)
+                },
             })
-            .map(|entry| Some(entry))
+            .map(Some)
             .collect();
         let lit = ArrayLit {
             span: DUMMY_SP,
@@ -61,12 +249,10 @@ impl From<&ArrayPat> for DestructArg {
     fn from(value: &ArrayPat) -> Self {
         let mut count = 0;
         let mut result = vec![];
-        for e in &value.elems {
-            if let Some(pat) = e {
-                if let Some(idents) = Self::idents_from(pat) {
-                    count += idents.len();
-                    result.extend(idents);
-                }
+        for pat in value.elems.iter().flatten() {
+            if let Some(idents) = Self::idents_from(pat) {
+                count += idents.len();
+                result.extend(idents);
             }
         }
         Self {
@@ -172,3 +358,42 @@ impl DestructArg {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn dep(name: &str, path: &[&str]) -> Dep {
+        Dep {
+            name: name.into(),
+            path: path.iter().map(|&p| p.into()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_equality_parse() {
+        assert_eq!(
+            Equality::parse(" @viewmill-eq shallow "),
+            Some(Ok(Equality::Shallow))
+        );
+        assert_eq!(
+            Equality::parse("@viewmill-eq sameIds"),
+            Some(Ok(Equality::Custom("sameIds".into())))
+        );
+        assert_eq!(Equality::parse("@viewmill-eq"), Some(Err(())));
+        assert_eq!(Equality::parse("@viewmill-eq a.b"), Some(Err(())));
+        assert_eq!(Equality::parse("@viewmill-equal shallow"), None);
+        assert_eq!(Equality::parse("shallow"), None);
+    }
+
+    #[test]
+    fn test_covers() {
+        assert!(dep("user", &[]).covers(&dep("user", &["name"])));
+        assert!(dep("user", &["name"]).covers(&dep("user", &["name"])));
+        assert!(dep("user", &["name"]).covers(&dep("user", &["name", "first"])));
+        assert!(!dep("user", &["name"]).covers(&dep("user", &[])));
+        assert!(!dep("user", &["name"]).covers(&dep("user", &["age"])));
+        assert!(!dep("user", &[]).covers(&dep("item", &[])));
+    }
+}
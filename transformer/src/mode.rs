@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+/// What kind of code views are transformed into.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputMode {
+    /// Views building and updating DOM in a browser
+    #[default]
+    Dom,
+    /// Views rendering static HTML strings via `renderToString()`, e.g. on a server
+    Ssr,
+    /// The same as `Dom`, but views are also able to adopt the server-rendered DOM via `hydrate()`
+    Hydrate,
+    /// The same as `Dom`, but every view is also defined as a custom element
+    Element,
+}
+
+const DOM: &str = "dom";
+const SSR: &str = "ssr";
+const HYDRATE: &str = "hydrate";
+const ELEMENT: &str = "element";
+
+impl FromStr for OutputMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            DOM => Ok(Self::Dom),
+            SSR => Ok(Self::Ssr),
+            HYDRATE => Ok(Self::Hydrate),
+            ELEMENT => Ok(Self::Element),
+            _ => Err(format!(
+                "Unknown output mode \"{s}\", expected \"{DOM}\", \"{SSR}\", \"{HYDRATE}\" or \"{ELEMENT}\""
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("dom".parse::<OutputMode>().unwrap(), OutputMode::Dom);
+        assert_eq!("SSR".parse::<OutputMode>().unwrap(), OutputMode::Ssr);
+        assert_eq!(
+            "hydrate".parse::<OutputMode>().unwrap(),
+            OutputMode::Hydrate
+        );
+        assert_eq!(
+            "element".parse::<OutputMode>().unwrap(),
+            OutputMode::Element
+        );
+        assert!("lorem ipsum".parse::<OutputMode>().is_err());
+    }
+}
@@ -1,19 +1,37 @@
 use std::collections::HashMap;
 use swc_core::ecma::{ast::*, atoms::JsWord};
 
-use super::utils::walk_every_pat_idents;
+use super::{
+    types::{walk_pat_types, ValueType},
+    utils::walk_every_pat_idents,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ScopeItem {
     Default,
+    /// A derived read-only live value
     Live,
+    /// A view parameter, which is live and writable
+    Param,
+    /// A view parameter of rendered views, which is a plain value
+    RenderedParam,
+    /// The runtime `state` function declaring a local state of a view
+    State,
+    /// The runtime module imported as a namespace
+    Runtime,
+    /// A named view of the module
+    View,
 }
 
 impl ScopeItem {
     fn is_live(&self) -> bool {
         match self {
-            ScopeItem::Default => false,
-            ScopeItem::Live => true,
+            ScopeItem::Default
+            | ScopeItem::RenderedParam
+            | ScopeItem::State
+            | ScopeItem::Runtime
+            | ScopeItem::View => false,
+            ScopeItem::Live | ScopeItem::Param => true,
         }
     }
 }
@@ -22,6 +40,7 @@ impl ScopeItem {
 pub struct Scope<'a> {
     parent: Option<&'a Scope<'a>>,
     map: HashMap<JsWord, ScopeItem>,
+    types: HashMap<JsWord, ValueType>,
     counted_set: HashMap<JsWord, u64>,
 }
 
@@ -30,13 +49,38 @@ impl Scope<'_> {
         self.get(name).map(|item| item.is_live()).unwrap_or(false)
     }
 
+    pub fn is_param(&self, name: &JsWord) -> bool {
+        matches!(
+            self.get(name),
+            Some(ScopeItem::Param | ScopeItem::RenderedParam)
+        )
+    }
+
+    pub fn is_state_fn(&self, name: &JsWord) -> bool {
+        matches!(self.get(name), Some(ScopeItem::State))
+    }
+
+    pub fn is_runtime(&self, name: &JsWord) -> bool {
+        matches!(self.get(name), Some(ScopeItem::Runtime))
+    }
+
+    pub fn is_view(&self, name: &JsWord) -> bool {
+        matches!(self.get(name), Some(ScopeItem::View))
+    }
+
+    /// Returns the known type of the closest declaration
+    pub fn type_of(&self, name: &JsWord) -> Option<&ValueType> {
+        if self.map.contains_key(name) {
+            self.types.get(name)
+        } else {
+            self.parent.and_then(|parent| parent.type_of(name))
+        }
+    }
+
     fn get(&self, name: &JsWord) -> Option<&ScopeItem> {
-        self.map.get(name).or_else(|| {
-            self.parent
-                .as_ref()
-                .map(|parent| parent.get(name))
-                .flatten()
-        })
+        self.map
+            .get(name)
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.get(name)))
     }
 }
 
@@ -47,6 +91,7 @@ impl Scope<'_> {
 
     pub fn insert_item(&mut self, name: &JsWord, item: ScopeItem) {
         self.map.insert(name.clone(), item);
+        self.types.remove(name);
         self.counted_set.insert(name.clone(), 0);
     }
 
@@ -54,6 +99,19 @@ impl Scope<'_> {
         walk_every_pat_idents(pat, |ident| self.insert_item(&ident.sym, item));
     }
 
+    /// Sets the known types of already inserted pattern idents
+    pub fn insert_pat_types(&mut self, pat: &Pat) {
+        walk_pat_types(pat, &mut |ident, ty| {
+            if let Some(ty) = ty {
+                self.set_type(&ident.sym, ty);
+            }
+        });
+    }
+
+    pub fn set_type(&mut self, name: &JsWord, ty: ValueType) {
+        self.types.insert(name.clone(), ty);
+    }
+
     pub fn insert_str_prefixed(&mut self, prefix: &str) -> JsWord {
         self.insert_prefixed(&prefix.into())
     }
@@ -77,7 +135,7 @@ impl Scope<'_> {
                     let mut count: Option<u64> = None;
                     let mut cur_parent = self.parent;
                     while let Some(parent) = cur_parent {
-                        if let Some(c) = parent.counted_set.get(&prefix) {
+                        if let Some(c) = parent.counted_set.get(prefix) {
                             count = Some(*c + 1);
                             break;
                         }
@@ -176,8 +234,8 @@ impl Scope<'_> {
         }
     }
 
-    pub fn insert_expr(&mut self, expr: &Box<Expr>) {
-        match &**expr {
+    pub fn insert_expr(&mut self, expr: &Expr) {
+        match expr {
             Expr::Fn(expr) => self.insert_fn_expr(expr),
             Expr::Class(expr) => self.insert_class_expr(expr),
             _ => (),
@@ -218,6 +276,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_param() {
+        let mut scope = Scope::default();
+        scope.insert_item(&jsw("foo"), ScopeItem::Param);
+        scope.insert_item(&jsw("bar"), ScopeItem::Live);
+        let mut scope = Scope::child_of(&scope);
+        scope.insert(&jsw("baz"));
+        assert!(scope.is_param(&jsw("foo")));
+        assert!(scope.is_live(&jsw("foo")));
+        assert!(!scope.is_param(&jsw("bar")));
+        assert!(scope.is_live(&jsw("bar")));
+        assert!(!scope.is_param(&jsw("baz")));
+        scope.insert(&jsw("foo"));
+        assert!(!scope.is_param(&jsw("foo")));
+    }
+
+    #[test]
+    fn test_type_of() {
+        let mut scope = Scope::default();
+        scope.insert(&jsw("foo"));
+        scope.set_type(&jsw("foo"), ValueType::Primitive);
+        scope.insert(&jsw("bar"));
+        let mut scope = Scope::child_of(&scope);
+        assert_eq!(scope.type_of(&jsw("foo")), Some(&ValueType::Primitive));
+        assert_eq!(scope.type_of(&jsw("bar")), None);
+        scope.insert(&jsw("foo"));
+        assert_eq!(scope.type_of(&jsw("foo")), None);
+    }
+
     #[test]
     fn test_insert_prefixed_without_offset() {
         let mut scope = Scope::default();
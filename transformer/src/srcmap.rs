@@ -0,0 +1,94 @@
+use std::{error::Error, str::FromStr};
+
+use swc_core::common::{source_map::SourceMapGenConfig, BytePos, FileName, LineCol, SourceMap};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SourceMapKind {
+    /// Appends the map to the output as a `data:` url comment.
+    Inline,
+    /// Returns the map in `Output::map`, so it's up to a caller where to
+    /// write it and how to reference it from the output.
+    Separate,
+}
+
+const INLINE: &str = "inline";
+const SEPARATE: &str = "separate";
+
+impl FromStr for SourceMapKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            INLINE => Ok(Self::Inline),
+            SEPARATE => Ok(Self::Separate),
+            _ => Err(format!(
+                "Unknown source map kind \"{s}\", expected \"{INLINE}\" or \"{SEPARATE}\""
+            )),
+        }
+    }
+}
+
+struct Config;
+
+impl SourceMapGenConfig for Config {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        match f {
+            FileName::Custom(name) => name.clone(),
+            _ => f.to_string(),
+        }
+    }
+
+    fn inline_sources_content(&self, _: &FileName) -> bool {
+        true
+    }
+}
+
+/// Builds a v3 source map json. The `line_offset` is a number of lines
+/// prepended to the emitted code (e.g. the file header).
+pub fn build(
+    cm: &SourceMap,
+    mut mappings: Vec<(BytePos, LineCol)>,
+    line_offset: u32,
+) -> Result<String, Box<dyn Error>> {
+    for (_, lc) in mappings.iter_mut() {
+        lc.line += line_offset;
+    }
+    let map = cm.build_source_map_with_config(&mappings, None, Config);
+    let mut json = Vec::new();
+    map.to_writer(&mut json)?;
+    Ok(String::from_utf8(json)?)
+}
+
+pub fn inline_comment(json: &str) -> String {
+    format!(
+        "//# sourceMappingURL=data:application/json;charset=utf-8;base64,{}",
+        base64::encode(json)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(
+            "inline".parse::<SourceMapKind>().unwrap(),
+            SourceMapKind::Inline
+        );
+        assert_eq!(
+            "Separate".parse::<SourceMapKind>().unwrap(),
+            SourceMapKind::Separate
+        );
+        assert!("lorem ipsum".parse::<SourceMapKind>().is_err());
+    }
+
+    #[test]
+    fn test_inline_comment() {
+        assert_eq!(
+            inline_comment("{}"),
+            "//# sourceMappingURL=data:application/json;charset=utf-8;base64,e30="
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use swc_core::ecma::{ast::*, atoms::JsWord};
+
+use super::scope::{Scope, ScopeItem};
+
+const STATE_FN: &str = "state";
+
+/// Inserts the `state` function imported from the runtime module, so views
+/// could declare their local state like `const [open, setOpen] = state(false)`.
+pub fn insert_runtime_imports(module: &Module, runtime: &str, scope: &mut Scope) {
+    for item in module.body.iter() {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) = item else {
+            continue;
+        };
+        if decl.type_only || &*decl.src.value != runtime {
+            continue;
+        }
+        for s in decl.specifiers.iter() {
+            match s {
+                ImportSpecifier::Named(s) if s.is_type_only => (),
+                ImportSpecifier::Named(s) => {
+                    let imported = match &s.imported {
+                        Some(ModuleExportName::Ident(ident)) => &ident.sym,
+                        Some(ModuleExportName::Str(s)) => &s.value,
+                        None => &s.local.sym,
+                    };
+                    if &**imported == STATE_FN {
+                        scope.insert_item(&s.local.sym, ScopeItem::State);
+                    }
+                }
+                ImportSpecifier::Namespace(s) => {
+                    scope.insert_item(&s.local.sym, ScopeItem::Runtime)
+                }
+                ImportSpecifier::Default(_) => (),
+            }
+        }
+    }
+}
+
+/// Checks for `state(...)` or `runtime.state(...)` calling the runtime function
+pub fn is_state_call(expr: &Expr, scope: &Scope) -> bool {
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = expr
+    else {
+        return false;
+    };
+    match &**callee {
+        Expr::Ident(ident) => scope.is_state_fn(&ident.sym),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => match &**obj {
+            Expr::Ident(ns) => &*prop.sym == STATE_FN && scope.is_runtime(&ns.sym),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The state value declared by the pattern like `[open, setOpen]`
+pub fn state_value(pat: &Pat) -> Option<&JsWord> {
+    let Pat::Array(arr) = pat else {
+        return None;
+    };
+    match arr.elems.first() {
+        Some(Some(Pat::Ident(binding))) => Some(&binding.id.sym),
+        _ => None,
+    }
+}
@@ -14,14 +14,12 @@ impl Syntax {
         const JSX: &str = "jsx";
         const TSX: &str = "tsx";
         path.extension()
-            .map(|ext| ext.to_str())
-            .flatten()
-            .map(|ext| match ext.to_lowercase().as_str() {
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| match ext.to_lowercase().as_str() {
                 JS | JSX => Some(Syntax::Js),
                 TS | TSX => Some(Syntax::Ts),
                 _ => None,
             })
-            .flatten()
     }
 
     pub fn ext(&self) -> &'static str {
@@ -1,27 +1,34 @@
 use swc_core::ecma::{ast::*, atoms::*};
 
-use super::{context::TrContext, errors::SpanError, jsx, live, scope::*, utils::*};
+use super::{
+    context::TrContext,
+    errors::{codes, SpanError},
+    jsx,
+    live::{self, Dep},
+    mode::OutputMode,
+    scope::*,
+    state::{is_state_call, state_value},
+    types::*,
+    utils::*,
+};
 
+#[derive(Default)]
 pub enum TrValue {
+    #[default]
     None,
-    Deps(Vec<JsWord>),
-}
-
-impl Default for TrValue {
-    fn default() -> Self {
-        Self::None
-    }
+    Deps(Vec<Dep>),
 }
 
 impl TrValue {
-    fn extend(&mut self, other: TrValue) {
+    pub fn extend(&mut self, other: TrValue) {
         match self {
             TrValue::None => *self = other,
             TrValue::Deps(deps) => match other {
                 TrValue::None => (),
                 TrValue::Deps(other) => {
                     for d in other {
-                        if !deps.contains(&d) {
+                        if !deps.iter().any(|dep| dep.covers(&d)) {
+                            deps.retain(|dep| !d.covers(dep));
                             deps.push(d);
                         }
                     }
@@ -34,24 +41,164 @@ impl TrValue {
 pub type TrResult = Result<TrValue, SpanError>;
 
 fn tr_var_decl(ctx: &TrContext, var: &mut Box<VarDecl>, scope: &mut Scope) -> TrResult {
+    let is_const = var.kind == VarDeclKind::Const;
+    let var_eq = ctx.equality_at(var.span.lo);
     for decl in var.decls.iter_mut() {
+        if decl
+            .init
+            .as_ref()
+            .is_some_and(|init| is_state_call(init, scope))
+        {
+            tr_state_decl(ctx, decl, scope)?;
+            continue;
+        }
         if let Some(init) = &mut decl.init {
-            let tr_result = tr_expr(ctx, init, &scope)?;
+            // Known before the live idents are replaced with their values
+            let types = pat_types(&decl.name, is_const.then_some(&**init), scope);
+            let tr_result = tr_expr(ctx, init, scope)?;
             match tr_result {
                 TrValue::None => (),
+                // Nothing changes while rendered, e.g. module lives
+                TrValue::Deps(_) if ctx.mode() == OutputMode::Ssr => (),
                 TrValue::Deps(deps) => {
-                    let var_init = live::var_initializer(ctx, &mut decl.name, init.clone(), &deps);
+                    let eq = ctx.equality_at(decl.span.lo).or(var_eq);
+                    let var_init =
+                        live::var_initializer(ctx, &mut decl.name, init.clone(), &deps, eq);
                     if let Some(var_init) = var_init {
                         *init = var_init;
                         scope.insert_pat_item(&decl.name, ScopeItem::Live);
                     }
                 }
             };
+            for (name, ty) in types {
+                scope.set_type(&name, ty);
+            }
         }
     }
     Ok(TrValue::None)
 }
 
+/// Declares a local state like `const [open, setOpen] = state(false)`, which
+/// is rendered as is, but becomes a live value and its setter otherwise.
+fn tr_state_decl(ctx: &TrContext, decl: &mut VarDeclarator, scope: &mut Scope) -> TrResult {
+    let Some(name) = state_value(&decl.name).cloned() else {
+        return Err(SpanError::new(
+            decl.span,
+            "Expected a local state declared like `const [value, setValue] = state(initial)`",
+            codes::INVALID_NODE,
+        ));
+    };
+    let Some(Expr::Call(call)) = decl.init.as_deref_mut() else {
+        return Ok(TrValue::None);
+    };
+    // Initial values are read once
+    for arg in call.args.iter_mut() {
+        tr_expr(ctx, &mut arg.expr, scope)?;
+    }
+    if ctx.mode() != OutputMode::Ssr {
+        decl.init = Some(ctx.local_state(std::mem::take(&mut call.args)));
+        scope.insert_item(&name, ScopeItem::Live);
+    }
+    Ok(TrValue::None)
+}
+
+/// Collects the types of the declared idents, inferring them from the
+/// initializer only if it's given (e.g. a `const` one).
+fn pat_types(pat: &Pat, init: Option<&Expr>, scope: &Scope) -> Vec<(JsWord, ValueType)> {
+    let mut types = vec![];
+    match pat {
+        Pat::Ident(binding) => {
+            let ty = binding
+                .type_ann
+                .as_ref()
+                .and_then(|ann| ValueType::from_ts_type(&ann.type_ann))
+                .or_else(|| init.and_then(|init| value_type_of(init, scope)));
+            if let Some(ty) = ty {
+                types.push((binding.id.sym.clone(), ty));
+            }
+        }
+        _ => walk_pat_types(pat, &mut |ident, ty| {
+            if let Some(ty) = ty {
+                types.push((ident.sym.clone(), ty));
+            }
+        }),
+    };
+    types
+}
+
+/// Checks if the expression always results in a primitive value, so it's
+/// rendered as a text node which is updated in place.
+pub fn is_primitive_expr(expr: &Expr, scope: &Scope) -> bool {
+    value_type_of(expr, scope).is_some_and(|ty| ty.is_primitive())
+}
+
+fn value_type_of(expr: &Expr, scope: &Scope) -> Option<ValueType> {
+    fn primitive_if(cond: bool) -> Option<ValueType> {
+        cond.then_some(ValueType::Primitive)
+    }
+
+    match expr {
+        Expr::Lit(lit) => primitive_if(!matches!(lit, Lit::Regex(_) | Lit::JSXText(_))),
+        Expr::Tpl(_) => Some(ValueType::Primitive),
+        Expr::Unary(_) => Some(ValueType::Primitive),
+        Expr::Update(_) => Some(ValueType::Primitive),
+        Expr::Bin(bin) => match bin.op {
+            op!("&&") | op!("||") | op!("??") => primitive_if(
+                is_primitive_expr(&bin.left, scope) && is_primitive_expr(&bin.right, scope),
+            ),
+            _ => Some(ValueType::Primitive),
+        },
+        Expr::Cond(cond) => primitive_if(
+            is_primitive_expr(&cond.cons, scope) && is_primitive_expr(&cond.alt, scope),
+        ),
+        Expr::Paren(p) => value_type_of(&p.expr, scope),
+        Expr::TsNonNull(nn) => value_type_of(&nn.expr, scope),
+        Expr::TsConstAssertion(a) => value_type_of(&a.expr, scope),
+        Expr::TsAs(a) => ValueType::from_ts_type(&a.type_ann),
+        Expr::TsTypeAssertion(a) => ValueType::from_ts_type(&a.type_ann),
+        Expr::TsSatisfies(s) => value_type_of(&s.expr, scope),
+        Expr::Ident(ident) => scope.type_of(&ident.sym).cloned(),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(prop) if &*prop.sym == "length" => Some(ValueType::Primitive),
+            MemberProp::Ident(prop) => {
+                value_type_of(&member.obj, scope).and_then(|ty| ty.member(&prop.sym).cloned())
+            }
+            _ => None,
+        },
+        Expr::Call(call) => match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => primitive_if(
+                    matches!(&*ident.sym, "String" | "Number" | "Boolean")
+                        && scope.type_of(&ident.sym).is_none()
+                        && !scope.is_live(&ident.sym),
+                ),
+                Expr::Member(MemberExpr {
+                    prop: MemberProp::Ident(prop),
+                    ..
+                }) => primitive_if(matches!(
+                    &*prop.sym,
+                    "toString"
+                        | "toFixed"
+                        | "toPrecision"
+                        | "toLocaleString"
+                        | "toUpperCase"
+                        | "toLowerCase"
+                        | "trim"
+                        | "trimStart"
+                        | "trimEnd"
+                        | "padStart"
+                        | "padEnd"
+                        | "join"
+                        | "repeat"
+                )),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn tr_decl(ctx: &TrContext, decl: &mut Decl, scope: &mut Scope) -> TrResult {
     Ok(match decl {
         Decl::Class(decl) => tr_class(ctx, &mut decl.class, scope)?,
@@ -74,7 +221,7 @@ pub fn tr_block_or_expr(
     scope: &Scope,
 ) -> TrResult {
     match &mut **input {
-        BlockStmtOrExpr::BlockStmt(block) => tr_block(ctx, block, scope),
+        BlockStmtOrExpr::BlockStmt(block) => tr_block(ctx, block, scope, false),
         BlockStmtOrExpr::Expr(expr) => {
             let mut scope = Scope::child_of(scope);
             scope.insert_expr(expr);
@@ -83,21 +230,45 @@ pub fn tr_block_or_expr(
     }
 }
 
-pub fn tr_block(ctx: &TrContext, block: &mut BlockStmt, scope: &Scope) -> TrResult {
+/// The body of a view, where statements like `console.log(count)` or
+/// `document.title = title` become effects re-run on every change.
+pub fn tr_view_body(ctx: &TrContext, input: &mut Box<BlockStmtOrExpr>, scope: &Scope) -> TrResult {
+    match &mut **input {
+        BlockStmtOrExpr::BlockStmt(block) => tr_block(ctx, block, scope, true),
+        BlockStmtOrExpr::Expr(_) => tr_block_or_expr(ctx, input, scope),
+    }
+}
+
+/// Expression statements become effects with `effects` set, see `tr_view_body`.
+pub fn tr_block(ctx: &TrContext, block: &mut BlockStmt, scope: &Scope, effects: bool) -> TrResult {
     let mut result = TrValue::default();
     let mut scope = Scope::child_of(scope);
     for stmt in &block.stmts {
         scope.insert_stmt(stmt);
     }
     for stmt in block.stmts.iter_mut() {
-        result.extend(tr_stmt(ctx, stmt, &mut scope)?);
+        let value = match stmt {
+            Stmt::Expr(stmt) if effects => match tr_expr(ctx, &mut stmt.expr, &scope) {
+                // Rendered views run once
+                Ok(TrValue::Deps(deps)) if ctx.mode() != OutputMode::Ssr => {
+                    stmt.expr = ctx.effect(stmt.expr.clone(), &deps);
+                    Ok(TrValue::None)
+                }
+                value => value,
+            },
+            _ => tr_stmt(ctx, stmt, &mut scope),
+        };
+        match value {
+            Ok(value) => result.extend(value),
+            Err(err) => err.emit(),
+        };
     }
     Ok(result)
 }
 
 fn tr_stmt(ctx: &TrContext, stmt: &mut Stmt, scope: &mut Scope) -> TrResult {
     Ok(match stmt {
-        Stmt::Block(block) => tr_block(ctx, block, scope)?,
+        Stmt::Block(block) => tr_block(ctx, block, scope, false)?,
         Stmt::Empty(_) => TrValue::None,
         Stmt::Debugger(_) => TrValue::None,
         Stmt::With(with) => tr_stmt(ctx, &mut with.body, scope)?,
@@ -134,16 +305,16 @@ fn tr_stmt(ctx: &TrContext, stmt: &mut Stmt, scope: &mut Scope) -> TrResult {
         Stmt::Throw(stmt) => tr_expr(ctx, &mut stmt.arg, scope)?,
         Stmt::Try(stmt) => {
             let mut result = TrValue::default();
-            result.extend(tr_block(ctx, &mut stmt.block, scope)?);
+            result.extend(tr_block(ctx, &mut stmt.block, scope, false)?);
             if let Some(handler) = &mut stmt.handler {
-                let mut scope = Scope::child_of(&scope);
+                let mut scope = Scope::child_of(scope);
                 if let Some(param) = &handler.param {
                     scope.insert_pat_item(param, ScopeItem::Default);
                 }
-                result.extend(tr_block(ctx, &mut handler.body, &scope)?);
+                result.extend(tr_block(ctx, &mut handler.body, &scope, false)?);
             }
             if let Some(finalizer) = &mut stmt.finalizer {
-                result.extend(tr_block(ctx, finalizer, &scope)?);
+                result.extend(tr_block(ctx, finalizer, scope, false)?);
             }
             result
         }
@@ -184,6 +355,7 @@ fn tr_stmt(ctx: &TrContext, stmt: &mut Stmt, scope: &mut Scope) -> TrResult {
         Stmt::ForIn(stmt) => {
             let mut result = TrValue::default();
             let mut scope = Scope::child_of(scope);
+            result.extend(tr_for_head(ctx, &mut stmt.left, &mut scope)?);
             result.extend(tr_expr(ctx, &mut stmt.right, &scope)?);
             result.extend(tr_stmt(ctx, &mut stmt.body, &mut scope)?);
             result
@@ -191,6 +363,7 @@ fn tr_stmt(ctx: &TrContext, stmt: &mut Stmt, scope: &mut Scope) -> TrResult {
         Stmt::ForOf(stmt) => {
             let mut result = TrValue::default();
             let mut scope = Scope::child_of(scope);
+            result.extend(tr_for_head(ctx, &mut stmt.left, &mut scope)?);
             result.extend(tr_expr(ctx, &mut stmt.right, &scope)?);
             result.extend(tr_stmt(ctx, &mut stmt.body, &mut scope)?);
             result
@@ -200,24 +373,88 @@ fn tr_stmt(ctx: &TrContext, stmt: &mut Stmt, scope: &mut Scope) -> TrResult {
     })
 }
 
+/// Declared loop variables shadow live values, otherwise the pattern is assigned
+fn tr_for_head(ctx: &TrContext, head: &mut ForHead, scope: &mut Scope) -> TrResult {
+    let decls = match head {
+        ForHead::VarDecl(var) => &var.decls,
+        ForHead::UsingDecl(using) => &using.decls,
+        ForHead::Pat(pat) => return tr_pat(ctx, pat, scope),
+    };
+    for decl in decls.iter() {
+        scope.insert_pat_item(&decl.name, ScopeItem::Default);
+    }
+    Ok(TrValue::None)
+}
+
+/// The member of a live value like `user.address?.city`, but a called method
+/// like `user.getName()` is a dependency on the whole object.
+fn member_dep(expr: &MemberExpr, scope: &Scope, is_callee: bool) -> Option<Dep> {
+    let mut path = vec![];
+    let mut expr = expr;
+    loop {
+        let MemberProp::Ident(prop) = &expr.prop else {
+            return None;
+        };
+        path.push(prop.sym.clone());
+        expr = match &*expr.obj {
+            Expr::Ident(ident) if scope.is_live(&ident.sym) => break Some(ident),
+            Expr::Member(member) => member,
+            Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+                OptChainBase::Member(member) => member,
+                OptChainBase::Call(_) => return None,
+            },
+            _ => return None,
+        };
+    }
+    .map(|ident| {
+        path.reverse();
+        if is_callee {
+            path.pop();
+        }
+        Dep {
+            name: ident.sym.clone(),
+            path,
+        }
+    })
+}
+
 pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult {
-    fn tr_member_expr(ctx: &TrContext, expr: &mut MemberExpr, scope: &Scope) -> TrResult {
+    fn tr_member_expr(
+        ctx: &TrContext,
+        expr: &mut MemberExpr,
+        scope: &Scope,
+        is_callee: bool,
+    ) -> TrResult {
+        // Known before the live ident is replaced with its value
+        let dep = member_dep(expr, scope, is_callee);
         let mut result = tr_expr(ctx, &mut expr.obj, scope)?;
         match &mut expr.prop {
             MemberProp::Ident(_) => (),
             MemberProp::PrivateName(_) => (),
             MemberProp::Computed(prop) => result.extend(tr_expr(ctx, &mut prop.expr, scope)?),
         };
-        Ok(result)
+        Ok(match dep {
+            Some(dep) => TrValue::Deps(vec![dep]),
+            None => result,
+        })
+    }
+
+    fn tr_callee(ctx: &TrContext, callee: &mut Box<Expr>, scope: &Scope) -> TrResult {
+        match &mut **callee {
+            Expr::Member(member) => tr_member_expr(ctx, member, scope, true),
+            Expr::OptChain(OptChainExpr { base, .. }) => match &mut **base {
+                OptChainBase::Member(member) => tr_member_expr(ctx, member, scope, true),
+                OptChainBase::Call(_) => tr_expr(ctx, callee, scope),
+            },
+            _ => tr_expr(ctx, callee, scope),
+        }
     }
 
     match &mut **expr {
         Expr::Array(array) => {
             let mut result = TrValue::default();
-            for el in array.elems.iter_mut() {
-                if let Some(el) = el {
-                    result.extend(tr_expr(ctx, &mut el.expr, scope)?);
-                }
+            for el in array.elems.iter_mut().flatten() {
+                result.extend(tr_expr(ctx, &mut el.expr, scope)?);
             }
             Ok(result)
         }
@@ -234,28 +471,27 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
                             if scope.is_live(sym) {
                                 let mut value = ident_expr(sym);
                                 result.extend(tr_expr(ctx, &mut value, scope)?);
-                                *prop = Box::new(Prop::KeyValue(KeyValueProp {
+                                **prop = Prop::KeyValue(KeyValueProp {
                                     key: PropName::Ident(ident(sym)),
                                     value,
-                                }));
+                                });
                             }
                         }
                         Prop::KeyValue(prop) => {
                             result.extend(tr_expr(ctx, &mut prop.value, scope)?)
                         }
                         Prop::Assign(_) => (),
-                        Prop::Getter(prop) => match &mut prop.body {
-                            Some(body) => {
-                                result.extend(tr_block(ctx, body, scope)?);
+                        Prop::Getter(prop) => {
+                            if let Some(body) = &mut prop.body {
+                                result.extend(tr_block(ctx, body, scope, false)?);
                             }
-                            None => (),
-                        },
+                        }
                         Prop::Setter(prop) => {
                             let mut scope = Scope::child_of(scope);
                             scope.insert_pat_item(&prop.param, ScopeItem::Default);
                             result.extend(tr_pat(ctx, &mut prop.param, &scope)?);
                             if let Some(body) = &mut prop.body {
-                                result.extend(tr_block(ctx, body, &mut scope)?);
+                                result.extend(tr_block(ctx, body, &scope, false)?);
                             }
                         }
                         Prop::Method(prop) => {
@@ -268,7 +504,10 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
         }
         Expr::Fn(expr) => tr_function(ctx, &mut expr.function, scope),
         Expr::Unary(expr) => tr_expr(ctx, &mut expr.arg, scope),
-        Expr::Update(upd) => tr_expr(ctx, &mut upd.arg, scope),
+        Expr::Update(upd) => match &*upd.arg {
+            Expr::Ident(ident) if scope.is_live(&ident.sym) => Err(reassignment_err(ident)),
+            _ => tr_expr(ctx, &mut upd.arg, scope),
+        },
         Expr::Bin(expr) => {
             let mut result = TrValue::default();
             result.extend(tr_expr(ctx, &mut expr.left, scope)?);
@@ -278,13 +517,18 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
         Expr::Assign(assign) => {
             let mut result = TrValue::default();
             match &mut assign.left {
-                PatOrExpr::Expr(expr) => result.extend(tr_expr(ctx, expr, scope)?),
+                PatOrExpr::Expr(expr) => match &**expr {
+                    Expr::Ident(ident) if scope.is_live(&ident.sym) => {
+                        return Err(reassignment_err(ident))
+                    }
+                    _ => result.extend(tr_expr(ctx, expr, scope)?),
+                },
                 PatOrExpr::Pat(pat) => result.extend(tr_pat(ctx, pat, scope)?),
             };
             result.extend(tr_expr(ctx, &mut assign.right, scope)?);
             Ok(result)
         }
-        Expr::Member(expr) => tr_member_expr(ctx, expr, scope),
+        Expr::Member(expr) => tr_member_expr(ctx, expr, scope, false),
         Expr::SuperProp(expr) => match &mut expr.prop {
             SuperProp::Ident(_) => Ok(TrValue::None),
             SuperProp::Computed(prop) => tr_expr(ctx, &mut prop.expr, scope),
@@ -301,7 +545,7 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
             match &mut expr.callee {
                 Callee::Super(_) => (),
                 Callee::Import(_) => (),
-                Callee::Expr(expr) => result.extend(tr_expr(ctx, expr, scope)?),
+                Callee::Expr(expr) => result.extend(tr_callee(ctx, expr, scope)?),
             };
             for arg in expr.args.iter_mut() {
                 result.extend(tr_expr(ctx, &mut arg.expr, scope)?);
@@ -328,8 +572,8 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
         Expr::Ident(ident) => {
             let sym = &ident.sym;
             if scope.is_live(sym) {
-                let result = TrValue::Deps(vec![sym.clone()]);
-                *expr = live::value_of(sym);
+                let result = TrValue::Deps(vec![Dep::from(sym)]);
+                *expr = live::value_of(sym, ident.span);
                 Ok(result)
             } else {
                 Ok(TrValue::None)
@@ -354,9 +598,9 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
             let mut scope = Scope::child_of(scope);
             for p in arrow.params.iter_mut() {
                 scope.insert_pat_item(p, ScopeItem::Default);
-                result.extend(tr_pat(ctx, p, &mut scope)?);
+                result.extend(tr_pat(ctx, p, &scope)?);
             }
-            result.extend(tr_block_or_expr(ctx, &mut arrow.body, &mut scope)?);
+            result.extend(tr_block_or_expr(ctx, &mut arrow.body, &scope)?);
             Ok(result)
         }
         Expr::Class(expr) => tr_class(ctx, &mut expr.class, scope),
@@ -384,10 +628,10 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
         Expr::TsSatisfies(s) => tr_expr(ctx, &mut s.expr, scope),
         Expr::PrivateName(_) => Ok(TrValue::None),
         Expr::OptChain(expr) => match &mut *expr.base {
-            OptChainBase::Member(expr) => tr_member_expr(ctx, expr, scope),
+            OptChainBase::Member(expr) => tr_member_expr(ctx, expr, scope, false),
             OptChainBase::Call(expr) => {
                 let mut result = TrValue::default();
-                result.extend(tr_expr(ctx, &mut expr.callee, scope)?);
+                result.extend(tr_callee(ctx, &mut expr.callee, scope)?);
                 for arg in expr.args.iter_mut() {
                     result.extend(tr_expr(ctx, &mut arg.expr, scope)?);
                 }
@@ -398,28 +642,36 @@ pub fn tr_expr(ctx: &TrContext, expr: &mut Box<Expr>, scope: &Scope) -> TrResult
         Expr::Lit(_) => Ok(TrValue::None),
         Expr::MetaProp(_) => Ok(TrValue::None),
         Expr::TsInstantiation(_) => Ok(TrValue::None),
-        Expr::Invalid(expr) => Err(SpanError::new(expr.span, "invalid node")),
+        Expr::Invalid(expr) => Err(SpanError::new(
+            expr.span,
+            "invalid node",
+            codes::INVALID_NODE,
+        )),
     }
 }
 
+/// Live values are replaced with their current values, so they can't be assigned
+fn reassignment_err(ident: &Ident) -> SpanError {
+    SpanError::new(
+        ident.span,
+        &format!(
+            "Cannot reassign \"{}\" depending on parameters, declare another variable instead",
+            ident.sym
+        ),
+        codes::INVALID_ASSIGNMENT,
+    )
+}
+
+/// Only patterns of assignments and function parameters get here, and the
+/// parameters shadow live values, so any live ident is reassigned.
 fn tr_pat(ctx: &TrContext, pat: &mut Pat, scope: &Scope) -> TrResult {
     match pat {
-        Pat::Ident(ident) => {
-            let sym = &ident.sym;
-            if scope.is_live(sym) {
-                let result = TrValue::Deps(vec![sym.clone()]);
-                *pat = Pat::Expr(live::value_of(sym));
-                Ok(result)
-            } else {
-                Ok(TrValue::None)
-            }
-        }
+        Pat::Ident(ident) if scope.is_live(&ident.sym) => Err(reassignment_err(ident)),
+        Pat::Ident(_) => Ok(TrValue::None),
         Pat::Array(arr) => {
             let mut result = TrValue::default();
-            for e in arr.elems.iter_mut() {
-                if let Some(e) = e {
-                    result.extend(tr_pat(ctx, e, scope)?);
-                }
+            for e in arr.elems.iter_mut().flatten() {
+                result.extend(tr_pat(ctx, e, scope)?);
             }
             Ok(result)
         }
@@ -431,6 +683,9 @@ fn tr_pat(ctx: &TrContext, pat: &mut Pat, scope: &Scope) -> TrResult {
                     ObjectPatProp::KeyValue(p) => {
                         result.extend(tr_pat(ctx, &mut p.value, scope)?);
                     }
+                    ObjectPatProp::Assign(p) if scope.is_live(&p.key.sym) => {
+                        return Err(reassignment_err(&p.key));
+                    }
                     ObjectPatProp::Assign(p) => {
                         if let Some(value) = &mut p.value {
                             result.extend(tr_expr(ctx, value, scope)?);
@@ -441,7 +696,11 @@ fn tr_pat(ctx: &TrContext, pat: &mut Pat, scope: &Scope) -> TrResult {
             }
             Ok(result)
         }
-        Pat::Assign(assign) => tr_expr(ctx, &mut assign.right, scope),
+        Pat::Assign(assign) => {
+            let mut result = tr_pat(ctx, &mut assign.left, scope)?;
+            result.extend(tr_expr(ctx, &mut assign.right, scope)?);
+            Ok(result)
+        }
         Pat::Invalid(_) => Ok(TrValue::None),
         Pat::Expr(expr) => tr_expr(ctx, expr, scope),
     }
@@ -455,7 +714,7 @@ fn tr_function(ctx: &TrContext, func: &mut Box<Function>, scope: &Scope) -> TrRe
         result.extend(tr_pat(ctx, &mut p.pat, &scope)?);
     }
     if let Some(body) = &mut func.body {
-        result.extend(tr_block(ctx, body, &mut scope)?);
+        result.extend(tr_block(ctx, body, &scope, false)?);
     }
     Ok(result)
 }
@@ -515,7 +774,7 @@ fn tr_class(ctx: &TrContext, class: &mut Box<Class>, scope: &Scope) -> TrResult
             ClassMember::TsIndexSignature(_) => (),
             ClassMember::Empty(_) => (),
             ClassMember::StaticBlock(member) => {
-                result.extend(tr_block(ctx, &mut member.body, scope)?)
+                result.extend(tr_block(ctx, &mut member.body, scope, false)?)
             }
             ClassMember::AutoAccessor(member) => {
                 if let Some(value) = &mut member.value {
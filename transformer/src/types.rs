@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use swc_core::ecma::{ast::*, atoms::JsWord};
+
+/// What's statically known about a value
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValueType {
+    /// A string, number, boolean, etc. which is rendered as text
+    Primitive,
+    /// An object with the known types of its properties
+    Object(HashMap<JsWord, ValueType>),
+}
+
+impl ValueType {
+    pub fn is_primitive(&self) -> bool {
+        matches!(self, ValueType::Primitive)
+    }
+
+    pub fn member(&self, name: &JsWord) -> Option<&ValueType> {
+        match self {
+            ValueType::Primitive => None,
+            ValueType::Object(props) => props.get(name),
+        }
+    }
+
+    pub fn from_ts_type(ty: &TsType) -> Option<Self> {
+        match ty {
+            TsType::TsKeywordType(kw) => match kw.kind {
+                TsKeywordTypeKind::TsStringKeyword
+                | TsKeywordTypeKind::TsNumberKeyword
+                | TsKeywordTypeKind::TsBooleanKeyword
+                | TsKeywordTypeKind::TsBigIntKeyword
+                | TsKeywordTypeKind::TsNullKeyword
+                | TsKeywordTypeKind::TsUndefinedKeyword => Some(ValueType::Primitive),
+                _ => None,
+            },
+            TsType::TsLitType(_) => Some(ValueType::Primitive),
+            TsType::TsParenthesizedType(ty) => Self::from_ts_type(&ty.type_ann),
+            TsType::TsOptionalType(ty) => Self::from_ts_type(&ty.type_ann),
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(u)) => {
+                let all_primitive = u
+                    .types
+                    .iter()
+                    .all(|ty| matches!(Self::from_ts_type(ty), Some(ValueType::Primitive)));
+                all_primitive.then_some(ValueType::Primitive)
+            }
+            TsType::TsTypeLit(lit) => {
+                let mut props = HashMap::new();
+                for m in lit.members.iter() {
+                    if let TsTypeElement::TsPropertySignature(TsPropertySignature {
+                        key,
+                        computed: false,
+                        type_ann: Some(ann),
+                        ..
+                    }) = m
+                    {
+                        if let (Expr::Ident(key), Some(ty)) =
+                            (&**key, Self::from_ts_type(&ann.type_ann))
+                        {
+                            props.insert(key.sym.clone(), ty);
+                        }
+                    }
+                }
+                Some(ValueType::Object(props))
+            }
+            _ => None,
+        }
+    }
+
+    /// Infers the type of a default value
+    fn from_default(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Lit(Lit::Str(_) | Lit::Num(_) | Lit::Bool(_) | Lit::BigInt(_)) => {
+                Some(ValueType::Primitive)
+            }
+            Expr::Tpl(_) => Some(ValueType::Primitive),
+            Expr::Unary(u) if u.op == op!(unary, "-") => Self::from_default(&u.arg),
+            _ => None,
+        }
+    }
+}
+
+fn type_ann(ann: &Option<Box<TsTypeAnn>>) -> Option<ValueType> {
+    ann.as_ref()
+        .and_then(|ann| ValueType::from_ts_type(&ann.type_ann))
+}
+
+/// Walks every identifier of the pattern along with its type if known
+pub fn walk_pat_types(pat: &Pat, cb: &mut impl FnMut(&Ident, Option<ValueType>)) {
+    walk(pat, None, cb)
+}
+
+fn walk(pat: &Pat, ty: Option<ValueType>, cb: &mut impl FnMut(&Ident, Option<ValueType>)) {
+    match pat {
+        Pat::Ident(binding) => cb(&binding.id, type_ann(&binding.type_ann).or(ty)),
+        Pat::Assign(assign) => {
+            let ty = ty.or_else(|| ValueType::from_default(&assign.right));
+            walk(&assign.left, ty, cb);
+        }
+        Pat::Object(obj) => {
+            let ty = type_ann(&obj.type_ann).or(ty);
+            let member = |key: &JsWord| ty.as_ref().and_then(|ty| ty.member(key)).cloned();
+            for p in obj.props.iter() {
+                match p {
+                    ObjectPatProp::KeyValue(p) => {
+                        let ty = match &p.key {
+                            PropName::Ident(key) => member(&key.sym),
+                            PropName::Str(key) => member(&key.value),
+                            _ => None,
+                        };
+                        walk(&p.value, ty, cb);
+                    }
+                    ObjectPatProp::Assign(p) => {
+                        let ty = member(&p.key.sym)
+                            .or_else(|| p.value.as_ref().and_then(|v| ValueType::from_default(v)));
+                        cb(&p.key, ty);
+                    }
+                    ObjectPatProp::Rest(p) => walk(&p.arg, None, cb),
+                }
+            }
+        }
+        Pat::Array(arr) => {
+            for p in arr.elems.iter().flatten() {
+                walk(p, None, cb);
+            }
+        }
+        Pat::Rest(rest) => walk(&rest.arg, None, cb),
+        Pat::Invalid(_) | Pat::Expr(_) => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use swc_core::common::DUMMY_SP;
+
+    use super::*;
+
+    fn kw(kind: TsKeywordTypeKind) -> TsType {
+        TsType::TsKeywordType(TsKeywordType {
+            span: DUMMY_SP,
+            kind,
+        })
+    }
+
+    fn union(types: Vec<TsType>) -> TsType {
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(TsUnionType {
+            span: DUMMY_SP,
+            types: types.into_iter().map(Box::new).collect(),
+        }))
+    }
+
+    #[test]
+    fn test_from_ts_type() {
+        use TsKeywordTypeKind::*;
+        assert_eq!(
+            ValueType::from_ts_type(&kw(TsNumberKeyword)),
+            Some(ValueType::Primitive)
+        );
+        assert_eq!(ValueType::from_ts_type(&kw(TsAnyKeyword)), None);
+        assert_eq!(
+            ValueType::from_ts_type(&union(vec![kw(TsStringKeyword), kw(TsUndefinedKeyword)])),
+            Some(ValueType::Primitive)
+        );
+        assert_eq!(
+            ValueType::from_ts_type(&union(vec![kw(TsStringKeyword), kw(TsObjectKeyword)])),
+            None
+        );
+    }
+}
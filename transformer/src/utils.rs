@@ -1,5 +1,5 @@
 use swc_core::{
-    common::{util::take::Take, DUMMY_SP},
+    common::{util::take::Take, Span, DUMMY_SP},
     ecma::{ast::*, atoms::JsWord},
 };
 
@@ -34,23 +34,29 @@ pub fn paren_expr(expr: Box<Expr>) -> Box<Expr> {
     }))
 }
 
-pub fn obj_method_call(
+pub fn spanned_obj_method_call(
+    span: Span,
     obj: Box<Expr>,
     method: &JsWord,
     args: Option<Vec<ExprOrSpread>>,
 ) -> Box<Expr> {
-    let expr = Expr::Call(CallExpr {
+    let callee = Expr::Member(MemberExpr {
         span: DUMMY_SP,
-        callee: {
-            let expr = MemberExpr {
-                span: DUMMY_SP,
-                obj,
-                prop: MemberProp::Ident(ident(method)),
-            };
-            let expr = Expr::Member(expr);
-            Callee::Expr(Box::new(expr))
-        },
-        args: args.unwrap_or_else(|| Take::dummy()),
+        obj,
+        prop: MemberProp::Ident(ident(method)),
+    });
+    spanned_fn_call(span, Box::new(callee), args)
+}
+
+pub fn spanned_fn_call(
+    span: Span,
+    callee: Box<Expr>,
+    args: Option<Vec<ExprOrSpread>>,
+) -> Box<Expr> {
+    let expr = Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(callee),
+        args: args.unwrap_or_else(Take::dummy),
         type_args: Take::dummy(),
     });
     Box::new(expr)
@@ -102,7 +108,7 @@ pub fn const_decl(name: &JsWord, init: Box<Expr>) -> Stmt {
 pub fn arrow_expr(params: Option<Vec<Pat>>, body: Box<BlockStmtOrExpr>) -> Box<Expr> {
     Box::new(Expr::Arrow(ArrowExpr {
         span: DUMMY_SP,
-        params: params.unwrap_or_else(|| Take::dummy()),
+        params: params.unwrap_or_else(Take::dummy),
         body,
         is_async: false,
         is_generator: false,
@@ -116,20 +122,41 @@ pub fn arrow_short_expr(params: Option<Vec<Pat>>, body: Box<Expr>) -> Box<Expr>
     arrow_expr(params, body)
 }
 
+pub fn array_expr(elems: impl IntoIterator<Item = Box<Expr>>) -> Box<Expr> {
+    Box::from(ArrayLit {
+        span: DUMMY_SP,
+        elems: elems
+            .into_iter()
+            .map(|expr| Some(ExprOrSpread { spread: None, expr }))
+            .collect(),
+    })
+}
+
 pub fn null_expr() -> Box<Expr> {
     let lit = Null { span: DUMMY_SP };
     Box::from(lit)
 }
 
+/// `void 0`, since `undefined` is just an identifier, which could be shadowed
+pub fn undefined_expr() -> Box<Expr> {
+    Box::from(UnaryExpr {
+        span: DUMMY_SP,
+        op: UnaryOp::Void,
+        arg: Box::from(Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: 0.0,
+            raw: None,
+        }))),
+    })
+}
+
 pub fn walk_every_pat_idents(pat: &Pat, mut cb: impl FnMut(&Ident)) {
     fn dive(pat: &Pat, cb: &mut impl FnMut(&Ident)) {
         match pat {
             Pat::Ident(ident) => cb(&ident.id),
             Pat::Array(arr) => {
-                for e in &arr.elems {
-                    if let Some(e) = e {
-                        dive(e, cb);
-                    }
+                for e in arr.elems.iter().flatten() {
+                    dive(e, cb);
                 }
             }
             Pat::Rest(rest) => dive(&rest.arg, cb),
@@ -188,18 +215,11 @@ impl From<Box<Expr>> for ArgsBuilder {
 
 // Object Literal Builder
 
+#[derive(Default)]
 pub struct ObjLitBuilder {
     props: Vec<PropOrSpread>,
 }
 
-impl Default for ObjLitBuilder {
-    fn default() -> Self {
-        Self {
-            props: Default::default(),
-        }
-    }
-}
-
 impl ObjLitBuilder {
     pub fn add_prop(&mut self, prop: Prop) -> &mut Self {
         self.props.push(PropOrSpread::Prop(Box::new(prop)));
@@ -0,0 +1,183 @@
+use std::{slice, str::FromStr};
+
+use swc_core::{
+    common::{comments::SingleThreadedComments, BytePos},
+    ecma::{ast::*, atoms::JsWord},
+};
+
+use super::scope::{Scope, ScopeItem};
+
+/// Which named exports are transformed into views. The default export is always a view.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum NamedViews {
+    #[default]
+    None,
+    /// Functions with capitalized names, e.g. `export const Foo = () => ...`
+    Capitalized,
+    /// Functions marked by the `/* @view */` comment
+    Marked,
+}
+
+const NONE: &str = "none";
+const CAPITALIZED: &str = "capitalized";
+const MARKED: &str = "marked";
+
+impl FromStr for NamedViews {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            NONE => Ok(Self::None),
+            CAPITALIZED => Ok(Self::Capitalized),
+            MARKED => Ok(Self::Marked),
+            _ => Err(format!(
+                "Unknown named views rule \"{s}\", expected \"{NONE}\", \"{CAPITALIZED}\" or \"{MARKED}\""
+            )),
+        }
+    }
+}
+
+const MARKER: &str = "@view";
+
+impl NamedViews {
+    /// Capitalized names only count for functions returning JSX, so helpers or
+    /// constructors like `export function Point(x, y)` are kept as is.
+    pub fn is_view(&self, name: &JsWord, marked: bool, returns_jsx: bool) -> bool {
+        match self {
+            NamedViews::None => false,
+            NamedViews::Capitalized => {
+                returns_jsx && name.chars().next().is_some_and(char::is_uppercase)
+            }
+            NamedViews::Marked => marked,
+        }
+    }
+}
+
+pub fn is_marked(comments: &SingleThreadedComments, pos: BytePos) -> bool {
+    comments.with_leading(pos, |list| list.iter().any(|c| c.text.trim() == MARKER))
+}
+
+/// Inserts the named exports transformed into views, so they aren't used as components
+pub fn insert_named_views(
+    module: &Module,
+    comments: &SingleThreadedComments,
+    named_views: NamedViews,
+    scope: &mut Scope,
+) {
+    for item in module.body.iter() {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(n)) = item else {
+            continue;
+        };
+        // The marker is expected either before `export` or before the declaration itself
+        let marked = |pos| is_marked(comments, pos);
+        match &n.decl {
+            Decl::Fn(decl) => {
+                let marked = marked(n.span.lo) || marked(decl.function.span.lo);
+                let returns_jsx = decl.function.body.as_ref().is_some_and(block_returns_jsx);
+                if named_views.is_view(&decl.ident.sym, marked, returns_jsx) {
+                    scope.insert_item(&decl.ident.sym, ScopeItem::View);
+                }
+            }
+            Decl::Var(var) => {
+                let marked = marked(n.span.lo) || marked(var.span.lo);
+                for decl in var.decls.iter() {
+                    let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) else {
+                        continue;
+                    };
+                    let returns_jsx = match &**init {
+                        Expr::Arrow(arrow) => match &*arrow.body {
+                            BlockStmtOrExpr::BlockStmt(block) => block_returns_jsx(block),
+                            BlockStmtOrExpr::Expr(expr) => is_jsx(expr),
+                        },
+                        Expr::Fn(expr) => {
+                            expr.function.body.as_ref().is_some_and(block_returns_jsx)
+                        }
+                        _ => continue,
+                    };
+                    if named_views.is_view(&name.id.sym, marked, returns_jsx) {
+                        scope.insert_item(&name.id.sym, ScopeItem::View);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn block_returns_jsx(block: &BlockStmt) -> bool {
+    returns_jsx(&block.stmts)
+}
+
+/// Looks for `return <...>` skipping nested functions
+fn returns_jsx(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => is_jsx(arg),
+        Stmt::Block(block) => block_returns_jsx(block),
+        Stmt::If(IfStmt { cons, alt, .. }) => {
+            returns_jsx(slice::from_ref(cons))
+                || alt
+                    .as_ref()
+                    .is_some_and(|alt| returns_jsx(slice::from_ref(alt)))
+        }
+        Stmt::Switch(SwitchStmt { cases, .. }) => cases.iter().any(|c| returns_jsx(&c.cons)),
+        Stmt::Try(stmt) => {
+            block_returns_jsx(&stmt.block)
+                || stmt
+                    .handler
+                    .as_ref()
+                    .is_some_and(|h| block_returns_jsx(&h.body))
+                || stmt.finalizer.as_ref().is_some_and(block_returns_jsx)
+        }
+        Stmt::Labeled(LabeledStmt { body, .. })
+        | Stmt::While(WhileStmt { body, .. })
+        | Stmt::DoWhile(DoWhileStmt { body, .. })
+        | Stmt::For(ForStmt { body, .. })
+        | Stmt::ForIn(ForInStmt { body, .. })
+        | Stmt::ForOf(ForOfStmt { body, .. }) => returns_jsx(slice::from_ref(body)),
+        _ => false,
+    })
+}
+
+fn is_jsx(expr: &Expr) -> bool {
+    match expr {
+        Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_jsx(expr),
+        Expr::Cond(CondExpr { cons, alt, .. }) => is_jsx(cons) || is_jsx(alt),
+        Expr::Bin(BinExpr {
+            op: BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing,
+            left,
+            right,
+            ..
+        }) => is_jsx(left) || is_jsx(right),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "Capitalized".parse::<NamedViews>().unwrap(),
+            NamedViews::Capitalized
+        );
+        assert_eq!("marked".parse::<NamedViews>().unwrap(), NamedViews::Marked);
+        assert_eq!("none".parse::<NamedViews>().unwrap(), NamedViews::None);
+        assert!("lorem ipsum".parse::<NamedViews>().is_err());
+    }
+
+    #[test]
+    fn test_is_view() {
+        let foo = JsWord::from("foo");
+        let bar = JsWord::from("Bar");
+        assert!(!NamedViews::None.is_view(&bar, true, true));
+        assert!(!NamedViews::Capitalized.is_view(&foo, true, true));
+        assert!(NamedViews::Capitalized.is_view(&bar, false, true));
+        assert!(!NamedViews::Capitalized.is_view(&bar, false, false));
+        assert!(NamedViews::Marked.is_view(&foo, true, false));
+        assert!(!NamedViews::Marked.is_view(&bar, false, true));
+    }
+}
@@ -0,0 +1,13 @@
+/* @element my-counter shadow */
+export default (count, label = "Count") => (
+    <p>{label}: {count}</p>
+);
+
+/* @view */
+export const MyBadge = (text) => <span>{text}</span>;
+
+/* @view */
+/* @element x-title */
+export function Title(value) {
+    return <h1>{value}</h1>;
+}
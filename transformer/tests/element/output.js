@@ -0,0 +1,60 @@
+import * as viewmill from "viewmill-runtime";
+export default function View(count, label = "Count") {
+    return viewmill.view({
+        count: viewmill.param(count),
+        label: viewmill.param(label)
+    }, ({
+        count,
+        label
+    }, unmountSignal)=>(viewmill.el("<p><!>: <!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(label.getValue()), [
+                label
+            ]), p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(count.getValue()), [
+                count
+            ]), p__1, anchor__2));
+        })));
+}
+export const MyBadge = function(text) {
+    return viewmill.view({
+        text: viewmill.param(text)
+    }, ({
+        text
+    }, unmountSignal)=>viewmill.el("<span><!></span>", (container, unmountSignal1)=>{
+            const span__1 = container.firstChild;
+            const anchor__1 = span__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(text.getValue()), [
+                text
+            ]), span__1, anchor__1));
+        }));
+};
+export function Title(value) {
+    return viewmill.view({
+        value: viewmill.param(value)
+    }, ({
+        value
+    }, unmountSignal)=>{
+        return viewmill.el("<h1><!></h1>", (container, unmountSignal1)=>{
+            const h1__1 = container.firstChild;
+            const anchor__1 = h1__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(value.getValue()), [
+                value
+            ]), h1__1, anchor__1));
+        });
+    });
+}
+viewmill.defineElement("my-counter", View, [
+    "count",
+    "label"
+], {
+    shadow: true
+});
+viewmill.defineElement("my-badge", MyBadge, [
+    "text"
+]);
+viewmill.defineElement("x-title", Title, [
+    "value"
+]);
@@ -0,0 +1,9 @@
+export default (title, user) => {
+    console.log("created");
+    console.log(user.name);
+    document.title = title;
+    if (title) {
+        console.log(title);
+    }
+    return <p>{title}</p>;
+};
@@ -0,0 +1,28 @@
+import * as viewmill from "viewmill-runtime";
+export default function(title, user) {
+    return viewmill.view({
+        title: viewmill.param(title),
+        user: viewmill.param(user)
+    }, ({
+        title,
+        user
+    }, unmountSignal)=>{
+        console.log("created");
+        viewmill.effect(()=>(console.log(user.getValue().name)), [
+            viewmill.member(user, "name")
+        ], unmountSignal);
+        viewmill.effect(()=>(document.title = title.getValue()), [
+            title
+        ], unmountSignal);
+        if (title.getValue()) {
+            console.log(title.getValue());
+        }
+        return viewmill.el("<p><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(title.getValue()), [
+                title
+            ]), p__1, anchor__1));
+        });
+    });
+};
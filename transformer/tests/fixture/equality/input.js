@@ -0,0 +1,19 @@
+import { sameIds } from "./utils";
+
+export default (a, b, items) => {
+    // @viewmill-eq shallow
+    const pair = [a, b];
+    // @viewmill-eq shallow
+    const { first, rest } = { first: a, rest: [b] };
+    const total = a + b,
+        // @viewmill-eq sameIds
+        selected = items.filter((it) => it.selected);
+    return (
+        <div>
+            <p>{pair.join(", ")}</p>
+            <p>{first}: {rest.length}</p>
+            <p>{total}</p>
+            <p>{selected.length}</p>
+        </div>
+    );
+};
@@ -0,0 +1,69 @@
+import * as viewmill_ from "viewmill-runtime";
+import { sameIds } from "./utils";
+export default function(a, b, items) {
+    return viewmill_.view({
+        a: viewmill_.param(a),
+        b: viewmill_.param(b),
+        items: viewmill_.param(items)
+    }, ({
+        a,
+        b,
+        items
+    }, unmountSignal)=>{
+        const pair = viewmill_.live(()=>([
+                a.getValue(),
+                b.getValue()
+            ]), [
+            a,
+            b
+        ], null, unmountSignal, viewmill_.shallowEqual);
+        const [first, rest] = viewmill_.live(()=>({
+                first: a.getValue(),
+                rest: [
+                    b.getValue()
+                ]
+            }), [
+            a,
+            b
+        ], [
+            2,
+            ({ first, rest })=>([
+                    first,
+                    rest
+                ])
+        ], unmountSignal, viewmill_.shallowEqual);
+        const total = viewmill_.live(()=>(a.getValue() + b.getValue()), [
+            a,
+            b
+        ], null, unmountSignal), selected = viewmill_.live(()=>(items.getValue().filter((it)=>it.selected)), [
+            items
+        ], null, unmountSignal, sameIds);
+        return (viewmill_.el("<div><p><!></p><p><!>: <!></p><p><!></p><p><!></p></div>", (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            const p__1 = div__1.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(pair.getValue().join(", ")), [
+                pair
+            ]), p__1, anchor__1));
+            const p__2 = p__1.nextSibling;
+            const anchor__2 = p__2.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.expr(()=>(first.getValue()), [
+                first
+            ]), p__2, anchor__2));
+            const anchor__3 = anchor__2.nextSibling.nextSibling;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(rest.getValue().length), [
+                viewmill_.member(rest, "length")
+            ]), p__2, anchor__3));
+            const p__3 = p__2.nextSibling;
+            const anchor__4 = p__3.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(total.getValue()), [
+                total
+            ]), p__3, anchor__4));
+            const p__4 = p__3.nextSibling;
+            const anchor__5 = p__4.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(selected.getValue().length), [
+                viewmill_.member(selected, "length")
+            ]), p__4, anchor__5));
+        }));
+    });
+};
@@ -0,0 +1,27 @@
+import * as viewmill from "viewmill-runtime";
+import { tooltip, autosize, draggable } from "./actions";
+export default function(text, axis) {
+    return viewmill.view({
+        text: viewmill.param(text),
+        axis: viewmill.param(axis)
+    }, ({
+        text,
+        axis
+    }, unmountSignal)=>(viewmill.el("<div><textarea></textarea><button>Save</button><p></p></div>", (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            viewmill.action(div__1, draggable, ()=>(axis.getValue()), [
+                axis
+            ], unmountSignal1);
+            const textarea__1 = div__1.firstChild;
+            viewmill.action(textarea__1, autosize, void 0, null, unmountSignal1);
+            const button__1 = textarea__1.nextSibling;
+            viewmill.action(button__1, tooltip, ()=>({
+                    text: text.getValue(),
+                    placement: "top"
+                }), [
+                text
+            ], unmountSignal1);
+            const p__1 = button__1.nextSibling;
+            viewmill.action(p__1, tooltip, "Static", null, unmountSignal1);
+        })));
+};
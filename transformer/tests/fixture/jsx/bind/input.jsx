@@ -0,0 +1,7 @@
+export default (text, done) => (
+    <form>
+        <input type="text" bind:value={text} />
+        <input type="checkbox" bind:checked={done} />
+        <p>{text}</p>
+    </form>
+);
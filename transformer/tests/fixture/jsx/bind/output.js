@@ -0,0 +1,27 @@
+import * as viewmill from "viewmill-runtime";
+export default function(text, done) {
+    return viewmill.view({
+        text: viewmill.param(text),
+        done: viewmill.param(done)
+    }, ({
+        text,
+        done
+    }, unmountSignal)=>(viewmill.el('<form><input type="text"/><input type="checkbox"/><p><!></p></form>', (container, unmountSignal1)=>{
+            const form__1 = container.firstChild;
+            const input__1 = form__1.firstChild;
+            viewmill.prop(input__1, "value", ()=>(text.getValue()), [
+                text
+            ], unmountSignal1);
+            viewmill.listen(input__1, "input", ()=>(text.setValue(input__1.value)));
+            const input__2 = input__1.nextSibling;
+            viewmill.prop(input__2, "checked", ()=>(done.getValue()), [
+                done
+            ], unmountSignal1);
+            viewmill.listen(input__2, "change", ()=>(done.setValue(input__2.checked)));
+            const p__1 = input__2.nextSibling;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(text.getValue()), [
+                text
+            ]), p__1, anchor__1));
+        })));
+};
@@ -22,7 +22,7 @@ export default function(a, b) {
                 const div__1 = container.firstChild;
                 viewmill.attr(div__1, "x", x);
                 const anchor__1 = div__1.firstChild;
-                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(c.getValue()), [
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(c.getValue()), [
                     c
                 ]), div__1, anchor__1));
                 const p__1 = anchor__1.nextSibling;
@@ -0,0 +1,9 @@
+export default (active, disabled, color, size) => (
+    <div class="box" class:active={active} class:static>
+        <p class={{ "is-disabled": disabled, wide: size > 10 }} style:color={color} />
+        <span style={{ fontSize: `${size}px`, "--accent": color, display: "block" }} />
+        <i class:hidden={false} />
+        <b style="margin: 0; padding: 0" style:margin={size} />
+        <a class={color} class:active={active} style={`padding: ${size}px`} style:color={color} />
+    </div>
+);
@@ -0,0 +1,57 @@
+import * as viewmill from "viewmill-runtime";
+export default function(active, disabled, color, size) {
+    return viewmill.view({
+        active: viewmill.param(active),
+        disabled: viewmill.param(disabled),
+        color: viewmill.param(color),
+        size: viewmill.param(size)
+    }, ({
+        active,
+        disabled,
+        color,
+        size
+    }, unmountSignal)=>(viewmill.el('<div class="box"><p></p><span></span><i></i><b style="margin: 0; padding: 0"></b><a></a></div>', (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            viewmill.toggleClass(div__1, "active", ()=>(active.getValue()), [
+                active
+            ], unmountSignal1);
+            viewmill.toggleClass(div__1, "static", true);
+            const p__1 = div__1.firstChild;
+            viewmill.toggleClass(p__1, "is-disabled", ()=>(disabled.getValue()), [
+                disabled
+            ], unmountSignal1);
+            viewmill.toggleClass(p__1, "wide", ()=>(size.getValue() > 10), [
+                size
+            ], unmountSignal1);
+            viewmill.style(p__1, "color", ()=>(color.getValue()), [
+                color
+            ], unmountSignal1);
+            const span__1 = p__1.nextSibling;
+            viewmill.style(span__1, "font-size", ()=>(`${size.getValue()}px`), [
+                size
+            ], unmountSignal1);
+            viewmill.style(span__1, "--accent", ()=>(color.getValue()), [
+                color
+            ], unmountSignal1);
+            viewmill.style(span__1, "display", "block");
+            const i__1 = span__1.nextSibling;
+            viewmill.toggleClass(i__1, "hidden", false);
+            const b__1 = i__1.nextSibling;
+            viewmill.style(b__1, "margin", ()=>(size.getValue()), [
+                size
+            ], unmountSignal1);
+            const a__1 = b__1.nextSibling;
+            viewmill.mergeClass(a__1, ()=>(color.getValue()), [
+                color
+            ], unmountSignal1);
+            viewmill.toggleClass(a__1, "active", ()=>(active.getValue()), [
+                active
+            ], unmountSignal1);
+            viewmill.mergeStyle(a__1, ()=>(`padding: ${size.getValue()}px`), [
+                size
+            ], unmountSignal1);
+            viewmill.style(a__1, "color", ()=>(color.getValue()), [
+                color
+            ], unmountSignal1);
+        })));
+};
@@ -26,7 +26,7 @@ export default function(a, b) {
             viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.cond(()=>(c.getValue()), ()=>(viewmill.el("<p>c = <!></p>", (container1, unmountSignal2)=>{
                     const p__2 = container1.firstChild;
                     const anchor__2 = p__2.firstChild.nextSibling;
-                    viewmill.unmountOn(unmountSignal2, viewmill.insert(viewmill.expr(()=>(c.getValue()), [
+                    viewmill.unmountOn(unmountSignal2, viewmill.insert(viewmill.text(()=>(c.getValue()), [
                         c
                     ]), p__2, anchor__2));
                 })), ()=>(null), [
@@ -39,7 +39,7 @@ export default function(a, b) {
             ]), div__1, anchor__3));
             const p__2 = anchor__3.nextSibling;
             const anchor__4 = p__2.firstChild;
-            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(a.getValue() + b.getValue() + c.getValue()), [
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(a.getValue() + b.getValue() + c.getValue()), [
                 a,
                 b,
                 c
@@ -0,0 +1,9 @@
+export default (a) => (
+    <div title='say "hi" & <bye>' data-a="&lt;&quot;&gt;">
+        a &amp; b &lt; c &gt; d & "e"
+        <p>{a} &copy; {"<b>"}</p>
+        <span />
+        <img alt="1 < 2" />
+        <input></input>
+    </div>
+);
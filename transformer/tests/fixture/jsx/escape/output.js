@@ -0,0 +1,20 @@
+import * as viewmill from "viewmill-runtime";
+export default function(a) {
+    return viewmill.view({
+        a: viewmill.param(a)
+    }, ({
+        a
+    }, unmountSignal)=>(viewmill.el('<div title="say &quot;hi&quot; &amp; <bye>" data-a="<&quot;>">a &amp; b &lt; c &gt; d &amp; "e"<p><!> \xa9 <!></p><span></span><img alt="1 < 2"/><input/></div>', (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            const p__1 = div__1.firstChild.nextSibling;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(a.getValue()), [
+                a
+            ]), p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert("<b>", p__1, anchor__2));
+            const span__1 = p__1.nextSibling;
+            const img__1 = span__1.nextSibling;
+            const input__1 = img__1.nextSibling;
+        })));
+};
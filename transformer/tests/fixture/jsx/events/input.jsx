@@ -0,0 +1,7 @@
+export default (onSubmit, label) => (
+    <form onSubmit={onSubmit} on:submit_prevent={() => console.log(label)}>
+        <input one="1" online onmyevent={onSubmit} oninput={(e) => console.log(e)} />
+        <button onDoubleClick={onSubmit} on:click_stop_once={onSubmit}>{label}</button>
+        <div on:my-event={onSubmit} on:wheel_passive_capture={onSubmit} />
+    </form>
+);
@@ -0,0 +1,49 @@
+import * as viewmill from "viewmill-runtime";
+export default function(onSubmit, label) {
+    return viewmill.view({
+        onSubmit: viewmill.param(onSubmit),
+        label: viewmill.param(label)
+    }, ({
+        onSubmit,
+        label
+    }, unmountSignal)=>(viewmill.el('<form><input one="1" online/><button><!></button><div></div></form>', (container, unmountSignal1)=>{
+            const form__1 = container.firstChild;
+            viewmill.listen(form__1, "submit", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1);
+            viewmill.listen(form__1, "submit", ()=>console.log(label.getValue()), [
+                label
+            ], unmountSignal1, {
+                prevent: true
+            });
+            const input__1 = form__1.firstChild;
+            viewmill.listen(input__1, "myevent", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1);
+            viewmill.listen(input__1, "input", (e)=>console.log(e));
+            const button__1 = input__1.nextSibling;
+            viewmill.listen(button__1, "dblclick", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1);
+            viewmill.listen(button__1, "click", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1, {
+                stop: true,
+                once: true
+            });
+            const anchor__1 = button__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(label.getValue()), [
+                label
+            ]), button__1, anchor__1));
+            const div__1 = button__1.nextSibling;
+            viewmill.listen(div__1, "my-event", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1);
+            viewmill.listen(div__1, "wheel", onSubmit.getValue(), [
+                onSubmit
+            ], unmountSignal1, {
+                passive: true,
+                capture: true
+            });
+        })));
+};
@@ -27,7 +27,7 @@ export default function(a, b) {
             viewmill.list(()=>(a.getValue()), [
                 a
             ]),
-            viewmill.expr(()=>(a.getValue() + b.getValue() + c), [
+            viewmill.text(()=>(a.getValue() + b.getValue() + c), [
                 a,
                 b
             ]),
@@ -0,0 +1,14 @@
+export default (items, selected) => (
+    <ul>
+        {...items.map((item, index) => (
+            <li key={item.id} class={selected === item.id ? "selected" : ""}>
+                {index}: {item.name}
+            </li>
+        ))}
+        {...items.map((item) => {
+            return <Row key="static" item={item} />;
+        })}
+        {...[1, 2, 3].map((n) => <li key={n}>{n}</li>)}
+        {...items.map((item) => <li>{item.name}</li>)}
+    </ul>
+);
@@ -0,0 +1,51 @@
+import * as viewmill from "viewmill-runtime";
+export default function(items, selected) {
+    return viewmill.view({
+        items: viewmill.param(items),
+        selected: viewmill.param(selected)
+    }, ({
+        items,
+        selected
+    }, unmountSignal)=>(viewmill.el("<ul><!><!><!><!></ul>", (container, unmountSignal1)=>{
+            const ul__1 = container.firstChild;
+            const anchor__1 = ul__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.keyed(()=>(items.getValue()), (item, index)=>item.id, (item, index)=>(viewmill.el("<li><!>: <!></li>", (container1, unmountSignal2)=>{
+                    const li__1 = container1.firstChild;
+                    viewmill.attr(li__1, "class", ()=>(selected.getValue() === item.id ? "selected" : ""), [
+                        selected
+                    ], unmountSignal2);
+                    const anchor__1 = li__1.firstChild;
+                    viewmill.unmountOn(unmountSignal2, viewmill.insert(index, li__1, anchor__1));
+                    const anchor__2 = anchor__1.nextSibling.nextSibling;
+                    viewmill.unmountOn(unmountSignal2, viewmill.insert(item.name, li__1, anchor__2));
+                })), [
+                items
+            ]), ul__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.keyed(()=>(items.getValue()), (item)=>"static", (item)=>{
+                return viewmill.cmp(Row, {
+                    item: item
+                });
+            }, [
+                items
+            ]), ul__1, anchor__2));
+            const anchor__3 = anchor__2.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.keyed(()=>([
+                    1,
+                    2,
+                    3
+                ]), (n)=>n, (n)=>viewmill.el("<li><!></li>", (container1, unmountSignal2)=>{
+                    const li__1 = container1.firstChild;
+                    const anchor__3 = li__1.firstChild;
+                    viewmill.unmountOn(unmountSignal2, viewmill.insert(n, li__1, anchor__3));
+                })), ul__1, anchor__3));
+            const anchor__4 = anchor__3.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.list(()=>(items.getValue().map((item)=>viewmill.el("<li><!></li>", (container1, unmountSignal2)=>{
+                        const li__1 = container1.firstChild;
+                        const anchor__4 = li__1.firstChild;
+                        viewmill.unmountOn(unmountSignal2, viewmill.insert(item.name, li__1, anchor__4));
+                    }))), [
+                items
+            ]), ul__1, anchor__4));
+        })));
+};
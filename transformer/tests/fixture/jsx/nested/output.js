@@ -15,7 +15,7 @@ export default function(a, b) {
             return viewmill.el("<span><!></span>", (container, unmountSignal1)=>{
                 const span__1 = container.firstChild;
                 const anchor__1 = span__1.firstChild;
-                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(a.getValue() + x + c.getValue()), [
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(a.getValue() + x + c.getValue()), [
                     a,
                     c
                 ]), span__1, anchor__1));
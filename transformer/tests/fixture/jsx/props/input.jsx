@@ -0,0 +1,15 @@
+export default (text, done, html) => (
+    <form>
+        <input type="text" value={text} placeholder={text} />
+        <input type="checkbox" checked={done} indeterminate={false} />
+        <input value="initial" checked />
+        <select>
+            <option value="a" selected={text === "a"}>A</option>
+        </select>
+        <select value={text}>
+            <option value="a">A</option>
+            {done && <option value="b">B</option>}
+        </select>
+        <div innerHTML={html} prop:title={text} prop:hidden prop:tabIndex="1" />
+    </form>
+);
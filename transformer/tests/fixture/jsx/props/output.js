@@ -0,0 +1,50 @@
+import * as viewmill from "viewmill-runtime";
+export default function(text, done, html) {
+    return viewmill.view({
+        text: viewmill.param(text),
+        done: viewmill.param(done),
+        html: viewmill.param(html)
+    }, ({
+        text,
+        done,
+        html
+    }, unmountSignal)=>(viewmill.el('<form><input type="text"/><input type="checkbox"/><input value="initial" checked/><select><option value="a">A</option></select><select><option value="a">A</option><!></select><div></div></form>', (container, unmountSignal1)=>{
+            const form__1 = container.firstChild;
+            const input__1 = form__1.firstChild;
+            viewmill.prop(input__1, "value", ()=>(text.getValue()), [
+                text
+            ], unmountSignal1);
+            viewmill.attr(input__1, "placeholder", ()=>(text.getValue()), [
+                text
+            ], unmountSignal1);
+            const input__2 = input__1.nextSibling;
+            viewmill.prop(input__2, "checked", ()=>(done.getValue()), [
+                done
+            ], unmountSignal1);
+            viewmill.prop(input__2, "indeterminate", false);
+            const input__3 = input__2.nextSibling;
+            const select__1 = input__3.nextSibling;
+            const option__1 = select__1.firstChild;
+            viewmill.prop(option__1, "selected", ()=>(text.getValue() === "a"), [
+                text
+            ], unmountSignal1);
+            const select__2 = select__1.nextSibling;
+            const option__2 = select__2.firstChild;
+            const anchor__1 = option__2.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.cond(()=>(done.getValue()), ()=>(viewmill.el('<option value="b">B</option>')), ()=>(null), [
+                done
+            ]), select__2, anchor__1));
+            viewmill.prop(select__2, "value", ()=>(text.getValue()), [
+                text
+            ], unmountSignal1);
+            const div__1 = select__2.nextSibling;
+            viewmill.prop(div__1, "innerHTML", ()=>(html.getValue()), [
+                html
+            ], unmountSignal1);
+            viewmill.prop(div__1, "title", ()=>(text.getValue()), [
+                text
+            ], unmountSignal1);
+            viewmill.prop(div__1, "hidden", true);
+            viewmill.prop(div__1, "tabIndex", "1");
+        })));
+};
@@ -0,0 +1,13 @@
+import { createRef } from "viewmill-runtime";
+
+export default (onInput, label) => {
+    const input = createRef();
+    const button = (node) => console.log(node);
+    return (
+        <form ref={onInput}>
+            <input ref:input />
+            <button ref={button}>{label}</button>
+            <p ref={(node) => node?.focus()} />
+        </form>
+    );
+};
@@ -0,0 +1,30 @@
+import * as viewmill_ from "viewmill-runtime";
+import { createRef } from "viewmill-runtime";
+export default function(onInput, label) {
+    return viewmill_.view({
+        onInput: viewmill_.param(onInput),
+        label: viewmill_.param(label)
+    }, ({
+        onInput,
+        label
+    }, unmountSignal)=>{
+        const input = createRef();
+        const button = (node)=>console.log(node);
+        return (viewmill_.el("<form><input/><button><!></button><p></p></form>", (container, unmountSignal1)=>{
+            const form__1 = container.firstChild;
+            viewmill_.ref(form__1, ()=>(onInput.getValue()), [
+                onInput
+            ], unmountSignal1);
+            const input__1 = form__1.firstChild;
+            viewmill_.ref(input__1, input, null, unmountSignal1);
+            const button__1 = input__1.nextSibling;
+            viewmill_.ref(button__1, button, null, unmountSignal1);
+            const anchor__1 = button__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.expr(()=>(label.getValue()), [
+                label
+            ]), button__1, anchor__1));
+            const p__1 = button__1.nextSibling;
+            viewmill_.ref(p__1, (node)=>node?.focus(), null, unmountSignal1);
+        }));
+    });
+};
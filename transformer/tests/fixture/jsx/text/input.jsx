@@ -0,0 +1,16 @@
+export default (count, items, user) => {
+    const double = count * 2;
+    const label = `Count: ${count}`;
+    return (
+        <p>
+            {count + 1}
+            {label}
+            {double}
+            {items.length}
+            {String(user)}
+            {user.name.toUpperCase()}
+            {user}
+            {user.name}
+        </p>
+    );
+};
@@ -0,0 +1,54 @@
+import * as viewmill from "viewmill-runtime";
+export default function(count, items, user) {
+    return viewmill.view({
+        count: viewmill.param(count),
+        items: viewmill.param(items),
+        user: viewmill.param(user)
+    }, ({
+        count,
+        items,
+        user
+    }, unmountSignal)=>{
+        const double = viewmill.live(()=>(count.getValue() * 2), [
+            count
+        ], null, unmountSignal);
+        const label = viewmill.live(()=>(`Count: ${count.getValue()}`), [
+            count
+        ], null, unmountSignal);
+        return (viewmill.el("<p><!><!><!><!><!><!><!><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(count.getValue() + 1), [
+                count
+            ]), p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(label.getValue()), [
+                label
+            ]), p__1, anchor__2));
+            const anchor__3 = anchor__2.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(double.getValue()), [
+                double
+            ]), p__1, anchor__3));
+            const anchor__4 = anchor__3.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(items.getValue().length), [
+                viewmill.member(items, "length")
+            ]), p__1, anchor__4));
+            const anchor__5 = anchor__4.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(String(user.getValue())), [
+                user
+            ]), p__1, anchor__5));
+            const anchor__6 = anchor__5.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(user.getValue().name.toUpperCase()), [
+                viewmill.member(user, "name")
+            ]), p__1, anchor__6));
+            const anchor__7 = anchor__6.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(user.getValue()), [
+                user
+            ]), p__1, anchor__7));
+            const anchor__8 = anchor__7.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(user.getValue().name), [
+                viewmill.member(user, "name")
+            ]), p__1, anchor__8));
+        }));
+    });
+};
@@ -23,7 +23,7 @@ export default function(a, b) {
                     b
                 ]), p__1, anchor__2));
                 const anchor__3 = anchor__2.nextSibling.nextSibling;
-                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(a.getValue() + b.getValue()), [
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(a.getValue() + b.getValue()), [
                     a,
                     b
                 ]), p__1, anchor__3));
@@ -58,14 +58,14 @@ export default function(a, b) {
                 ], div__1, anchor__2));
             }),
             [
-                "&nbsp;",
+                "\xa0",
                 viewmill.expr(()=>(b.getValue()), [
                     b
                 ]),
-                "&nbsp; &nbsp;",
+                "\xa0 \xa0",
                 viewmill.el("<span>&nbsp;text</span>"),
                 viewmill.el("<p>text&nbsp;</p>"),
-                "&nbsp;"
+                "\xa0"
             ],
             viewmill.el("<div>Newline text Newline text Newline text</div>"),
             [
@@ -0,0 +1,13 @@
+export default (user, key) => {
+    const city = user.address?.city;
+    return (
+        <div title={user.name.first + " " + user.name.last}>
+            <p>{user.getName()}</p>
+            <p>{user.tags.join(", ")}</p>
+            <p>{user[key]}</p>
+            <p>{user.age > 18 && user.age}</p>
+            <p>{user.name.first + user}</p>
+            <p>{city}</p>
+        </div>
+    );
+};
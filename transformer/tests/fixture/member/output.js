@@ -0,0 +1,52 @@
+import * as viewmill from "viewmill-runtime";
+export default function(user, key) {
+    return viewmill.view({
+        user: viewmill.param(user),
+        key: viewmill.param(key)
+    }, ({
+        user,
+        key
+    }, unmountSignal)=>{
+        const city = viewmill.live(()=>(user.getValue().address?.city), [
+            viewmill.member(user, "address", "city")
+        ], null, unmountSignal);
+        return (viewmill.el("<div><p><!></p><p><!></p><p><!></p><p><!></p><p><!></p><p><!></p></div>", (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            viewmill.attr(div__1, "title", ()=>(user.getValue().name.first + " " + user.getValue().name.last), [
+                viewmill.member(user, "name", "first"),
+                viewmill.member(user, "name", "last")
+            ], unmountSignal1);
+            const p__1 = div__1.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(user.getValue().getName()), [
+                user
+            ]), p__1, anchor__1));
+            const p__2 = p__1.nextSibling;
+            const anchor__2 = p__2.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(user.getValue().tags.join(", ")), [
+                viewmill.member(user, "tags")
+            ]), p__2, anchor__2));
+            const p__3 = p__2.nextSibling;
+            const anchor__3 = p__3.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(user.getValue()[key.getValue()]), [
+                user,
+                key
+            ]), p__3, anchor__3));
+            const p__4 = p__3.nextSibling;
+            const anchor__4 = p__4.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.cond(()=>(user.getValue().age > 18), ()=>(user.getValue().age), ()=>(null), [
+                viewmill.member(user, "age")
+            ]), p__4, anchor__4));
+            const p__5 = p__4.nextSibling;
+            const anchor__5 = p__5.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(user.getValue().name.first + user.getValue()), [
+                user
+            ]), p__5, anchor__5));
+            const p__6 = p__5.nextSibling;
+            const anchor__6 = p__6.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(city.getValue()), [
+                city
+            ]), p__6, anchor__6));
+        }));
+    });
+};
@@ -0,0 +1,21 @@
+/* @live */
+import { theme, locale } from "./settings";
+import { /* @live */ user, format } from "./store";
+import { count } from "./counter";
+import * as viewmill from "viewmill-runtime";
+
+/* @live */
+export const size = viewmill.param(12);
+
+const /* @live */ title = viewmill.param("Title");
+
+export default () => {
+    const label = `${user.name} (${locale})`;
+    return (
+        <div class={theme} style={{ fontSize: size }}>
+            <h1>{title}</h1>
+            <p>{label}</p>
+            <p>{format(count)}</p>
+        </div>
+    );
+};
@@ -0,0 +1,37 @@
+import * as viewmill_ from "viewmill-runtime";
+import { theme, locale } from "./settings";
+import { user, format } from "./store";
+import { count } from "./counter";
+import * as viewmill from "viewmill-runtime";
+export const size = viewmill.param(12);
+const title = viewmill.param("Title");
+export default function() {
+    return viewmill_.view({}, ({}, unmountSignal)=>{
+        const label = viewmill_.live(()=>(`${user.getValue().name} (${locale.getValue()})`), [
+            viewmill_.member(user, "name"),
+            locale
+        ], null, unmountSignal);
+        return (viewmill_.el("<div><h1><!></h1><p><!></p><p><!></p></div>", (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            viewmill_.mergeClass(div__1, ()=>(theme.getValue()), [
+                theme
+            ], unmountSignal1);
+            viewmill_.style(div__1, "font-size", ()=>(size.getValue()), [
+                size
+            ], unmountSignal1);
+            const h1__1 = div__1.firstChild;
+            const anchor__1 = h1__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.expr(()=>(title.getValue()), [
+                title
+            ]), h1__1, anchor__1));
+            const p__1 = h1__1.nextSibling;
+            const anchor__2 = p__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(label.getValue()), [
+                label
+            ]), p__1, anchor__2));
+            const p__2 = p__1.nextSibling;
+            const anchor__3 = p__2.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(format(count), p__2, anchor__3));
+        }));
+    });
+};
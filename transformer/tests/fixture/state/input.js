@@ -0,0 +1,20 @@
+import { state } from "viewmill-runtime";
+import * as rt from "viewmill-runtime";
+
+export default (items, initialTab) => {
+    const [open, setOpen] = state(false);
+    const [tab, setTab] = rt.state(initialTab);
+    const label = open ? "Close" : "Open";
+    return (
+        <div>
+            <button onclick={() => setOpen(!open)}>{label}</button>
+            {open && (
+                <ul>
+                    {items.map((it, i) => (
+                        <li onclick={() => setTab(i)}>{i === tab ? <b>{it}</b> : it}</li>
+                    ))}
+                </ul>
+            )}
+        </div>
+    );
+};
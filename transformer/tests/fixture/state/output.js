@@ -0,0 +1,50 @@
+import * as viewmill_ from "viewmill-runtime";
+import { state } from "viewmill-runtime";
+import * as rt from "viewmill-runtime";
+export default function(items, initialTab) {
+    return viewmill_.view({
+        items: viewmill_.param(items),
+        initialTab: viewmill_.param(initialTab)
+    }, ({
+        items,
+        initialTab
+    }, unmountSignal)=>{
+        const [open, setOpen] = viewmill_.localState(false);
+        const [tab, setTab] = viewmill_.localState(initialTab.getValue());
+        const label = viewmill_.live(()=>(open.getValue() ? "Close" : "Open"), [
+            open
+        ], null, unmountSignal);
+        return (viewmill_.el("<div><button><!></button><!></div>", (container, unmountSignal1)=>{
+            const div__1 = container.firstChild;
+            const button__1 = div__1.firstChild;
+            viewmill_.listen(button__1, "click", ()=>setOpen(!open.getValue()), [
+                open
+            ], unmountSignal1);
+            const anchor__1 = button__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(label.getValue()), [
+                label
+            ]), button__1, anchor__1));
+            const anchor__2 = button__1.nextSibling;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.cond(()=>(open.getValue()), ()=>((viewmill_.el("<ul><!></ul>", (container1, unmountSignal2)=>{
+                    const ul__1 = container1.firstChild;
+                    const anchor__2 = ul__1.firstChild;
+                    viewmill_.unmountOn(unmountSignal2, viewmill_.insert(viewmill_.expr(()=>(items.getValue().map((it, i)=>(viewmill_.el("<li><!></li>", (container2, unmountSignal3)=>{
+                                const li__1 = container2.firstChild;
+                                viewmill_.listen(li__1, "click", ()=>setTab(i));
+                                const anchor__2 = li__1.firstChild;
+                                viewmill_.unmountOn(unmountSignal3, viewmill_.insert(viewmill_.cond(()=>(i === tab.getValue()), ()=>(viewmill_.el("<b><!></b>", (container3, unmountSignal4)=>{
+                                        const b__1 = container3.firstChild;
+                                        const anchor__2 = b__1.firstChild;
+                                        viewmill_.unmountOn(unmountSignal4, viewmill_.insert(it, b__1, anchor__2));
+                                    })), ()=>(it), [
+                                    tab
+                                ]), li__1, anchor__2));
+                            })))), [
+                        items
+                    ]), ul__1, anchor__2));
+                }))), ()=>(null), [
+                open
+            ]), div__1, anchor__2));
+        }));
+    });
+};
@@ -14,8 +14,8 @@ export default function<T, U extends Array<T>>(a: T, b: U) {
                 a
             ]), span__1, anchor__1));
             const anchor__2 = anchor__1.nextSibling.nextSibling;
-            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(b.getValue().length), [
-                b
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(b.getValue().length), [
+                viewmill.member(b, "length")
             ]), span__1, anchor__2));
         });
     });
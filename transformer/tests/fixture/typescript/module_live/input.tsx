@@ -0,0 +1,13 @@
+import * as viewmill from "viewmill-runtime";
+import { Live } from "viewmill-runtime";
+import type { Settings } from "./settings";
+
+declare const settings: Live<Settings>;
+export const online: viewmill.Param<boolean> = viewmill.param(true);
+const plain: number = 1;
+
+export default (name: string) => (
+    <p class={settings.theme}>
+        {name} is {online ? "online" : "offline"} {plain}
+    </p>
+);
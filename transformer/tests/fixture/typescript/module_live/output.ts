@@ -0,0 +1,29 @@
+import * as viewmill_ from "viewmill-runtime";
+import * as viewmill from "viewmill-runtime";
+import { Live } from "viewmill-runtime";
+import { Settings } from "./settings";
+declare const settings: Live<Settings>;
+export const online: viewmill.Param<boolean> = viewmill.param(true);
+const plain: number = 1;
+export default function(name: string) {
+    return viewmill_.view({
+        name: viewmill_.param(name)
+    }, ({
+        name
+    }, unmountSignal)=>(viewmill_.el("<p><!> is <!> <!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            viewmill_.attr(p__1, "class", ()=>(settings.getValue().theme), [
+                viewmill_.member(settings, "theme")
+            ], unmountSignal1);
+            const anchor__1 = p__1.firstChild;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.text(()=>(name.getValue()), [
+                name
+            ]), p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling.nextSibling;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(viewmill_.cond(()=>(online.getValue()), ()=>("online"), ()=>("offline"), [
+                online
+            ]), p__1, anchor__2));
+            const anchor__3 = anchor__2.nextSibling.nextSibling;
+            viewmill_.unmountOn(unmountSignal1, viewmill_.insert(plain, p__1, anchor__3));
+        })));
+};
@@ -7,10 +7,10 @@ export default function(a: number, b?: boolean) {
         a,
         b
     }, unmountSignal)=>([
-            viewmill.expr(()=>(a.getValue()), [
+            viewmill.text(()=>(a.getValue()), [
                 a
             ]),
-            viewmill.expr(()=>(b.getValue()), [
+            viewmill.text(()=>(b.getValue()), [
                 b
             ])
         ]));
@@ -0,0 +1,20 @@
+export default (
+    title: string,
+    { count = 0, user }: { count: number, user: { name: string, tags: string[] } },
+    status: "on" | "off" | undefined,
+    data: unknown,
+) => {
+    const total: number = count * 2;
+    return (
+        <p>
+            {title}
+            {count}
+            {user.name}
+            {user.tags}
+            {status ?? "none"}
+            {total}
+            {data}
+            {data as string}
+        </p>
+    );
+};
@@ -0,0 +1,61 @@
+import * as viewmill from "viewmill-runtime";
+export default function(title: string, { count = 0, user }: {
+    count: number;
+    user: {
+        name: string;
+        tags: string[];
+    };
+}, status: "on" | "off" | undefined, data: unknown) {
+    return viewmill.view({
+        title: viewmill.param(title),
+        count: viewmill.param(count),
+        user: viewmill.param(user),
+        status: viewmill.param(status),
+        data: viewmill.param(data)
+    }, ({
+        title,
+        count,
+        user,
+        status,
+        data
+    }, unmountSignal)=>{
+        const total: number = viewmill.live(()=>(count.getValue() * 2), [
+            count
+        ], null, unmountSignal);
+        return (viewmill.el("<p><!><!><!><!><!><!><!><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(title.getValue()), [
+                title
+            ]), p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(count.getValue()), [
+                count
+            ]), p__1, anchor__2));
+            const anchor__3 = anchor__2.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(user.getValue().name), [
+                viewmill.member(user, "name")
+            ]), p__1, anchor__3));
+            const anchor__4 = anchor__3.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(user.getValue().tags), [
+                viewmill.member(user, "tags")
+            ]), p__1, anchor__4));
+            const anchor__5 = anchor__4.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(status.getValue() ?? "none"), [
+                status
+            ]), p__1, anchor__5));
+            const anchor__6 = anchor__5.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(total.getValue()), [
+                total
+            ]), p__1, anchor__6));
+            const anchor__7 = anchor__6.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(data.getValue()), [
+                data
+            ]), p__1, anchor__7));
+            const anchor__8 = anchor__7.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(data.getValue() as string), [
+                data
+            ]), p__1, anchor__8));
+        }));
+    });
+};
@@ -0,0 +1,9 @@
+export default (title, items) => (
+    <>
+        <h1 class="title">{title}</h1>
+        <ul onclick={() => console.log(title)}>
+            {...items.map((item) => <li key={item.id}>{item.name}</li>)}
+        </ul>
+        <footer>Total: {items.length}</footer>
+    </>
+);
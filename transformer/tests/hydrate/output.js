@@ -0,0 +1,39 @@
+import * as viewmill from "viewmill-runtime";
+export default function(title, items) {
+    return viewmill.view({
+        title: viewmill.param(title),
+        items: viewmill.param(items)
+    }, ({
+        title,
+        items
+    }, unmountSignal)=>([
+            viewmill.adopt('<h1 class="title"><!></h1>', (node, unmountSignal1)=>{
+                const h1__1 = node;
+                const anchor__1 = viewmill.anchor(h1__1.firstChild);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(title.getValue()), [
+                    title
+                ]), h1__1, anchor__1));
+            }),
+            viewmill.adopt("<ul><!></ul>", (node, unmountSignal1)=>{
+                const ul__1 = node;
+                viewmill.listen(ul__1, "click", ()=>console.log(title.getValue()), [
+                    title
+                ], unmountSignal1);
+                const anchor__1 = viewmill.anchor(ul__1.firstChild);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.keyed(()=>(items.getValue()), (item)=>item.id, (item)=>viewmill.adopt("<li><!></li>", (node1, unmountSignal2)=>{
+                        const li__1 = node1;
+                        const anchor__1 = viewmill.anchor(li__1.firstChild);
+                        viewmill.unmountOn(unmountSignal2, viewmill.insert(item.name, li__1, anchor__1));
+                    }), [
+                    items
+                ]), ul__1, anchor__1));
+            }),
+            viewmill.adopt("<footer>Total: <!></footer>", (node, unmountSignal1)=>{
+                const footer__1 = node;
+                const anchor__1 = viewmill.anchor(footer__1.firstChild.nextSibling);
+                viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(items.getValue().length), [
+                    viewmill.member(items, "length")
+                ]), footer__1, anchor__1));
+            })
+        ]));
+};
@@ -0,0 +1,10 @@
+import { el } from "./dom";
+
+export default (label, items) => {
+    const count = items.length;
+    return (
+        <label onclick={() => el(label)}>
+            {label}: {count}
+        </label>
+    );
+};
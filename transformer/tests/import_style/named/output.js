@@ -0,0 +1,29 @@
+import { el as el_, expr, insert, listen, live, member, param, text, unmountOn, view } from "viewmill-runtime";
+import { el } from "./dom";
+export default function(label, items) {
+    return view({
+        label: param(label),
+        items: param(items)
+    }, ({
+        label,
+        items
+    }, unmountSignal)=>{
+        const count = live(()=>(items.getValue().length), [
+            member(items, "length")
+        ], null, unmountSignal);
+        return (el_("<label><!>: <!></label>", (container, unmountSignal1)=>{
+            const label__1 = container.firstChild;
+            listen(label__1, "click", ()=>el(label.getValue()), [
+                label
+            ], unmountSignal1);
+            const anchor__1 = label__1.firstChild;
+            unmountOn(unmountSignal1, insert(expr(()=>(label.getValue()), [
+                label
+            ]), label__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling.nextSibling;
+            unmountOn(unmountSignal1, insert(text(()=>(count.getValue()), [
+                count
+            ]), label__1, anchor__2));
+        }));
+    });
+};
@@ -0,0 +1,12 @@
+import format, { round as r } from "./format";
+import * as icons from "./icons";
+import "./styles";
+import theme from "./theme";
+
+export const PRECISION = 2;
+
+export { format, theme, LATER as later };
+
+export default (a) => <p>{icons.prefix}{format(r(a, PRECISION))}</p>;
+
+export const LATER = 3;
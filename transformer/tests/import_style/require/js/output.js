@@ -0,0 +1,48 @@
+Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+const viewmill = require("./vendor/viewmill-runtime.js");
+const format_module = require("./format");
+const format = format_module && format_module.__esModule ? format_module.default : format_module;
+const { round: r } = format_module;
+const icons = require("./icons");
+require("./styles");
+const theme_module = require("./theme");
+const theme = theme_module && theme_module.__esModule ? theme_module.default : theme_module;
+const PRECISION = 2;
+Object.defineProperty(exports, "PRECISION", {
+    enumerable: true,
+    get: ()=>(PRECISION)
+});
+Object.defineProperty(exports, "format", {
+    enumerable: true,
+    get: ()=>(format)
+});
+Object.defineProperty(exports, "theme", {
+    enumerable: true,
+    get: ()=>(theme)
+});
+Object.defineProperty(exports, "later", {
+    enumerable: true,
+    get: ()=>(LATER)
+});
+exports.default = function(a) {
+    return viewmill.view({
+        a: viewmill.param(a)
+    }, ({
+        a
+    }, unmountSignal)=>viewmill.el("<p><!><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(icons.prefix, p__1, anchor__1));
+            const anchor__2 = anchor__1.nextSibling;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(format(r(a.getValue(), PRECISION))), [
+                a
+            ]), p__1, anchor__2));
+        }));
+};
+const LATER = 3;
+Object.defineProperty(exports, "LATER", {
+    enumerable: true,
+    get: ()=>(LATER)
+});
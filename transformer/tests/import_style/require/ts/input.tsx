@@ -0,0 +1 @@
+export default (a: number) => <p>{a}</p>;
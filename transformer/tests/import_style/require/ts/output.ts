@@ -0,0 +1,14 @@
+import viewmill = require("./vendor/viewmill-runtime.js");
+export default function(a: number) {
+    return viewmill.view({
+        a: viewmill.param(a)
+    }, ({
+        a
+    }, unmountSignal)=>viewmill.el("<p><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(a.getValue()), [
+                a
+            ]), p__1, anchor__1));
+        }));
+};
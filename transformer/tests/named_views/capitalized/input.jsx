@@ -0,0 +1,22 @@
+export const Title = (text) => <h1>{text}</h1>;
+
+export function Item({ label }, selected) {
+    return <li class={selected ? "selected" : ""}>{label}</li>;
+}
+
+export const List = function (items) {
+    return <ul>{items}</ul>;
+};
+
+export const format = (text) => text.toUpperCase();
+
+export function helper(a) {
+    return a;
+}
+
+export function Point(x, y) {
+    this.x = x;
+    this.y = y;
+}
+
+export default (title) => <header>{title}</header>;
@@ -0,0 +1,70 @@
+import * as viewmill from "viewmill-runtime";
+export const Title = function(text) {
+    return viewmill.view({
+        text: viewmill.param(text)
+    }, ({
+        text
+    }, unmountSignal)=>viewmill.el("<h1><!></h1>", (container, unmountSignal1)=>{
+            const h1__1 = container.firstChild;
+            const anchor__1 = h1__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(text.getValue()), [
+                text
+            ]), h1__1, anchor__1));
+        }));
+};
+export function Item({ label }, selected) {
+    return viewmill.view({
+        label: viewmill.param(label),
+        selected: viewmill.param(selected)
+    }, ({
+        label,
+        selected
+    }, unmountSignal)=>{
+        return viewmill.el("<li><!></li>", (container, unmountSignal1)=>{
+            const li__1 = container.firstChild;
+            viewmill.attr(li__1, "class", ()=>(selected.getValue() ? "selected" : ""), [
+                selected
+            ], unmountSignal1);
+            const anchor__1 = li__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(label.getValue()), [
+                label
+            ]), li__1, anchor__1));
+        });
+    });
+}
+export const List = function(items) {
+    return viewmill.view({
+        items: viewmill.param(items)
+    }, ({
+        items
+    }, unmountSignal)=>{
+        return viewmill.el("<ul><!></ul>", (container, unmountSignal1)=>{
+            const ul__1 = container.firstChild;
+            const anchor__1 = ul__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(items.getValue()), [
+                items
+            ]), ul__1, anchor__1));
+        });
+    });
+};
+export const format = (text)=>text.toUpperCase();
+export function helper(a) {
+    return a;
+}
+export function Point(x, y) {
+    this.x = x;
+    this.y = y;
+}
+export default function(title) {
+    return viewmill.view({
+        title: viewmill.param(title)
+    }, ({
+        title
+    }, unmountSignal)=>viewmill.el("<header><!></header>", (container, unmountSignal1)=>{
+            const header__1 = container.firstChild;
+            const anchor__1 = header__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.expr(()=>(title.getValue()), [
+                title
+            ]), header__1, anchor__1));
+        }));
+};
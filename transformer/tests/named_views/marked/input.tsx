@@ -0,0 +1,11 @@
+/* @view */
+export const title = (text: string) => <h1>{text}</h1>;
+
+export /* @view */ function item(label: string) {
+    return <li>{label}</li>;
+}
+
+export const Foo = (text: string) => text.toUpperCase();
+
+// @view
+export const bar = (a: number, b: number) => <p>{a + b}</p>;
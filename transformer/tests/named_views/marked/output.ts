@@ -0,0 +1,46 @@
+import * as viewmill from "viewmill-runtime";
+export const title = function(text: string) {
+    return viewmill.view({
+        text: viewmill.param(text)
+    }, ({
+        text
+    }, unmountSignal)=>viewmill.el("<h1><!></h1>", (container, unmountSignal1)=>{
+            const h1__1 = container.firstChild;
+            const anchor__1 = h1__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(text.getValue()), [
+                text
+            ]), h1__1, anchor__1));
+        }));
+};
+export function item(label: string) {
+    return viewmill.view({
+        label: viewmill.param(label)
+    }, ({
+        label
+    }, unmountSignal)=>{
+        return viewmill.el("<li><!></li>", (container, unmountSignal1)=>{
+            const li__1 = container.firstChild;
+            const anchor__1 = li__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(label.getValue()), [
+                label
+            ]), li__1, anchor__1));
+        });
+    });
+}
+export const Foo = (text: string)=>text.toUpperCase();
+export const bar = function(a: number, b: number) {
+    return viewmill.view({
+        a: viewmill.param(a),
+        b: viewmill.param(b)
+    }, ({
+        a,
+        b
+    }, unmountSignal)=>viewmill.el("<p><!></p>", (container, unmountSignal1)=>{
+            const p__1 = container.firstChild;
+            const anchor__1 = p__1.firstChild;
+            viewmill.unmountOn(unmountSignal1, viewmill.insert(viewmill.text(()=>(a.getValue() + b.getValue()), [
+                a,
+                b
+            ]), p__1, anchor__1));
+        }));
+};
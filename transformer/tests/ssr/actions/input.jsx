@@ -0,0 +1,9 @@
+import { tooltip, autosize, draggable } from "./actions";
+
+export default (text, axis) => (
+    <div use:draggable={axis}>
+        <textarea use:autosize />
+        <button use:tooltip={{ text, placement: "top" }}>Save</button>
+        <p use:tooltip="Static" />
+    </div>
+);
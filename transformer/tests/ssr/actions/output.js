@@ -0,0 +1,11 @@
+import * as viewmill from "viewmill-runtime";
+import { tooltip, autosize, draggable } from "./actions";
+export default function(text, axis) {
+    return viewmill.serverView({
+        text,
+        axis
+    }, ({
+        text,
+        axis
+    })=>(viewmill.html("<div><textarea></textarea><button>Save</button><p></p></div>")));
+};
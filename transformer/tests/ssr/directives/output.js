@@ -0,0 +1,86 @@
+import * as viewmill from "viewmill-runtime";
+export default function(active, disabled, color, size) {
+    return viewmill.serverView({
+        active,
+        disabled,
+        color,
+        size
+    }, ({
+        active,
+        disabled,
+        color,
+        size
+    })=>(viewmill.html("<div" + viewmill.classToHtml([
+            [
+                "box",
+                true
+            ],
+            [
+                "active",
+                active
+            ],
+            [
+                "static",
+                true
+            ]
+        ]) + "><p" + viewmill.classToHtml([
+            [
+                "is-disabled",
+                disabled
+            ],
+            [
+                "wide",
+                size > 10
+            ]
+        ]) + viewmill.styleToHtml([
+            [
+                "color",
+                color
+            ]
+        ]) + "></p><span" + viewmill.styleToHtml([
+            [
+                "font-size",
+                `${size}px`
+            ],
+            [
+                "--accent",
+                color
+            ],
+            [
+                "display",
+                "block"
+            ]
+        ]) + "></span><i" + viewmill.classToHtml([
+            [
+                "hidden",
+                false
+            ]
+        ]) + "></i><b" + viewmill.styleToHtml([
+            [
+                null,
+                "margin: 0; padding: 0"
+            ],
+            [
+                "margin",
+                size
+            ]
+        ]) + "></b><a" + viewmill.classToHtml([
+            [
+                color,
+                true
+            ],
+            [
+                "active",
+                active
+            ]
+        ]) + viewmill.styleToHtml([
+            [
+                null,
+                `padding: ${size}px`
+            ],
+            [
+                "color",
+                color
+            ]
+        ]) + "></a></div>")));
+};
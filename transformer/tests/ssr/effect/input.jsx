@@ -0,0 +1,4 @@
+export default (title) => {
+    document.title = title;
+    return <p>{title}</p>;
+};
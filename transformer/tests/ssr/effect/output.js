@@ -0,0 +1,11 @@
+import * as viewmill from "viewmill-runtime";
+export default function(title) {
+    return viewmill.serverView({
+        title
+    }, ({
+        title
+    })=>{
+        document.title = title;
+        return viewmill.html("<p><!--[-->" + viewmill.toHtml(title) + "<!--]--></p>");
+    });
+};
@@ -0,0 +1,18 @@
+import Item from "./item";
+
+export default (title, items, done, html) => {
+    const count = items.length;
+    return (
+        <div class="list" title={title} onclick={() => console.log(title)}>
+            <h1>{title} & {count > 0 ? <b>{count}</b> : "none"}</h1>
+            <input type="checkbox" bind:checked={done} />
+            <ul {...{ hidden: !count }}>
+                {...items.map((item) => <li key={item.id}>{item.name}</li>)}
+            </ul>
+            {done && <Item label={title} />}
+            <div innerHTML={html}>ignored</div>
+            <p textContent={title} prop:tabIndex={1} />
+            <>{title}</>
+        </div>
+    );
+};
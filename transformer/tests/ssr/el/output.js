@@ -0,0 +1,24 @@
+import * as viewmill from "viewmill-runtime";
+import Item from "./item";
+export default function(title, items, done, html) {
+    return viewmill.serverView({
+        title,
+        items,
+        done,
+        html
+    }, ({
+        title,
+        items,
+        done,
+        html
+    })=>{
+        const count = items.length;
+        return (viewmill.html('<div class="list"' + viewmill.attrToHtml("title", title) + "><h1><!--[-->" + viewmill.toHtml(title) + "<!--]--> &amp; <!--[-->" + viewmill.toHtml(count > 0 ? viewmill.html("<b><!--[-->" + viewmill.toHtml(count) + "<!--]--></b>") : "none") + '<!--]--></h1><input type="checkbox"' + viewmill.attrToHtml("checked", done) + "/><ul" + viewmill.attrsToHtml({
+            hidden: !count
+        }) + "><!--[-->" + viewmill.toHtml(items.map((item)=>viewmill.html("<li><!--[-->" + viewmill.toHtml(item.name) + "<!--]--></li>"))) + "<!--]--></ul><!--[-->" + viewmill.toHtml(done ? viewmill.cmp(Item, {
+            label: title
+        }) : null) + "<!--]--><div>" + viewmill.toHtml(viewmill.html(html)) + "</div><p>" + viewmill.toHtml(title) + "</p><!--[-->" + viewmill.toHtml([
+            title
+        ]) + "<!--]--></div>"));
+    });
+};
@@ -0,0 +1,11 @@
+/* @live */
+import { user } from "./store";
+
+export default (greeting) => {
+    const name = user.name;
+    return (
+        <p title={user.id}>
+            {greeting}, {name && user.name}!
+        </p>
+    );
+};
@@ -0,0 +1,12 @@
+import * as viewmill from "viewmill-runtime";
+import { user } from "./store";
+export default function(greeting) {
+    return viewmill.serverView({
+        greeting
+    }, ({
+        greeting
+    })=>{
+        const name = user.getValue().name;
+        return (viewmill.html("<p" + viewmill.attrToHtml("title", user.getValue().id) + "><!--[-->" + viewmill.toHtml(greeting) + "<!--]-->, <!--[-->" + viewmill.toHtml(name && user.getValue().name) + "<!--]-->!</p>"));
+    });
+};
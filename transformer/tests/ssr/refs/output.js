@@ -0,0 +1,15 @@
+import * as viewmill_ from "viewmill-runtime";
+import { createRef } from "viewmill-runtime";
+export default function(onInput, label) {
+    return viewmill_.serverView({
+        onInput,
+        label
+    }, ({
+        onInput,
+        label
+    })=>{
+        const input = createRef();
+        const button = (node)=>console.log(node);
+        return (viewmill_.html("<form><input/><button><!--[-->" + viewmill_.toHtml(label) + "<!--]--></button><p></p></form>"));
+    });
+};
@@ -0,0 +1,6 @@
+import { state as useState } from "viewmill-runtime";
+
+export default (title) => {
+    const [open, setOpen] = useState(false);
+    return <button onclick={() => setOpen(!open)}>{open ? title : "Open"}</button>;
+};
@@ -0,0 +1,12 @@
+import * as viewmill_ from "viewmill-runtime";
+import { state as useState } from "viewmill-runtime";
+export default function(title) {
+    return viewmill_.serverView({
+        title
+    }, ({
+        title
+    })=>{
+        const [open, setOpen] = useState(false);
+        return viewmill_.html("<button><!--[-->" + viewmill_.toHtml(open ? title : "Open") + "<!--]--></button>");
+    });
+};
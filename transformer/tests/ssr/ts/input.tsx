@@ -0,0 +1,6 @@
+export default (name: string, age?: number) => (
+    <>
+        <p>Hello, {name}!</p>
+        {age}
+    </>
+);
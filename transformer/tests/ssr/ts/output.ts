@@ -0,0 +1,13 @@
+import * as viewmill from "viewmill-runtime";
+export default function(name: string, age?: number) {
+    return viewmill.serverView({
+        name,
+        age
+    }, ({
+        name,
+        age
+    })=>([
+            viewmill.html("<p>Hello, <!--[-->" + viewmill.toHtml(name) + "<!--]-->!</p>"),
+            age
+        ]));
+};
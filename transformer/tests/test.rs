@@ -9,23 +9,33 @@ use swc_core::{
 
 use transformer::*;
 
-#[test]
-fn test_fixtures() {
-    fn tr_file_path(input: &Path) -> Output {
-        let tr_opts = Options {
-            syntax: Syntax::from_path(input).unwrap(),
-            target: EsVersion::Es5,
-            can_emit_warnings: true,
-        };
+/// Start lines and codes of the reported diagnostics
+fn diagnostic_lines(input: &str, opts: Options) -> Vec<(usize, String)> {
+    let Diagnostics(list) = tr_str(input, opts).err().unwrap();
+    list.iter()
+        .map(|d| (d.range.unwrap().start.line, d.code.clone().unwrap()))
+        .collect()
+}
+
+/// The expected `diagnostic_lines`
+fn lines<'a>(list: impl IntoIterator<Item = (usize, &'a str)>) -> Vec<(usize, String)> {
+    list.into_iter()
+        .map(|(line, code)| (line, code.to_string()))
+        .collect()
+}
+
+fn test_fixture_dir(dir: &str, make_opts: impl Fn(&Path) -> Options) {
+    let tr_file_path = |input: &Path| -> Output {
+        let tr_opts = make_opts(input);
         Tester::run(|tester| {
             let fm = tester.cm.load_file(input).unwrap();
             Ok(tr_file(&fm, tester.cm.clone(), tr_opts, false).unwrap())
         })
-    }
-    fn visit(path: &Path) {
+    };
+    fn visit(path: &Path, tr_file_path: &dyn Fn(&Path) -> Output) {
         if path.is_dir() {
             for entry in fs::read_dir(path).unwrap() {
-                visit(entry.unwrap().path().as_path());
+                visit(entry.unwrap().path().as_path(), tr_file_path);
             }
         } else if path
             .file_name()
@@ -50,5 +60,261 @@ fn test_fixtures() {
             }
         }
     }
-    _ = visit(&Path::new("./tests"));
+    visit(Path::new(dir), &tr_file_path);
+}
+
+fn fixture_options(input: &Path) -> Options {
+    Options {
+        syntax: Syntax::from_path(input).unwrap(),
+        target: EsVersion::Es5,
+        can_emit_warnings: true,
+        source_map: None,
+        named_views: NamedViews::None,
+        runtime: "viewmill-runtime".to_string(),
+        import_style: ImportStyle::Namespace,
+        mode: OutputMode::Dom,
+    }
+}
+
+#[test]
+fn test_fixtures() {
+    test_fixture_dir("./tests/fixture", fixture_options);
+}
+
+#[test]
+fn test_named_views() {
+    test_fixture_dir("./tests/named_views/capitalized", |input| Options {
+        named_views: NamedViews::Capitalized,
+        ..fixture_options(input)
+    });
+    test_fixture_dir("./tests/named_views/marked", |input| Options {
+        named_views: NamedViews::Marked,
+        ..fixture_options(input)
+    });
+}
+
+#[test]
+fn test_import_style() {
+    test_fixture_dir("./tests/import_style/named", |input| Options {
+        import_style: ImportStyle::Named,
+        ..fixture_options(input)
+    });
+    test_fixture_dir("./tests/import_style/require", |input| Options {
+        runtime: "./vendor/viewmill-runtime.js".to_string(),
+        import_style: ImportStyle::Require,
+        ..fixture_options(input)
+    });
+}
+
+#[test]
+fn test_ssr() {
+    test_fixture_dir("./tests/ssr", |input| Options {
+        mode: OutputMode::Ssr,
+        ..fixture_options(input)
+    });
+}
+
+#[test]
+fn test_hydrate() {
+    test_fixture_dir("./tests/hydrate", |input| Options {
+        mode: OutputMode::Hydrate,
+        ..fixture_options(input)
+    });
+}
+
+#[test]
+fn test_element() {
+    test_fixture_dir("./tests/element", |input| Options {
+        mode: OutputMode::Element,
+        named_views: NamedViews::Marked,
+        ..fixture_options(input)
+    });
+}
+
+#[test]
+fn test_source_map() {
+    let input = "export default (a) => {\n    return <p>{a}</p>;\n};\n";
+    let mut tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    tr_opts.source_map = Some(SourceMapKind::Separate);
+    let output = tr_named_str("input.jsx", input, tr_opts).unwrap();
+    let map = sourcemap::SourceMap::from_slice(output.map.unwrap().as_bytes()).unwrap();
+    assert_eq!(map.get_source(0), Some("input.jsx"));
+    assert_eq!(map.get_source_contents(0), Some(input));
+    let find = |needle: &str| {
+        let (line, s) = output
+            .src
+            .lines()
+            .enumerate()
+            .find(|(_, s)| s.contains(needle))
+            .unwrap();
+        let col = s.find(needle).unwrap();
+        map.lookup_token(line as u32, col as u32).unwrap()
+    };
+    let token = find("viewmill.el(");
+    assert_eq!((token.get_src_line(), token.get_src_col()), (1, 11));
+    let token = find("a.getValue()");
+    assert_eq!((token.get_src_line(), token.get_src_col()), (1, 15));
+
+    let mut tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    tr_opts.source_map = Some(SourceMapKind::Inline);
+    let output = tr_str(input, tr_opts).unwrap();
+    assert!(output.map.is_none());
+    assert!(output
+        .src
+        .trim_end()
+        .lines()
+        .last()
+        .unwrap()
+        .starts_with("//# sourceMappingURL=data:application/json;"));
+}
+
+#[test]
+fn test_diagnostics() {
+    let input = "export default (a) => {\n    return <p>{a</p>;\n};\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    let Diagnostics(list) = tr_named_str("input.jsx", input, tr_opts).err().unwrap();
+    assert_eq!(list.len(), 1);
+    let d = &list[0];
+    assert_eq!(d.severity, Severity::Error);
+    assert_eq!(d.code.as_deref(), Some("syntax"));
+    assert_eq!(d.file.as_deref(), Some("input.jsx"));
+    assert_eq!(d.range.unwrap().start.line, 2);
+}
+
+#[test]
+fn test_invalid_nesting() {
+    let input = "export default () => (\n    <div>\n        <p><div /></p>\n        <table><tr /></table>\n        <br>text</br>\n    </div>\n);\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([
+            (3, "invalid-nesting"),
+            (4, "invalid-nesting"),
+            (5, "invalid-nesting")
+        ])
+    );
+}
+
+#[test]
+fn test_invalid_binding() {
+    let input = "export default (a, b) => {\n    const c = a + 1;\n    const d = 1;\n    return <form>\n        <input bind:value={c} />\n        <input bind:value={d} />\n        <input bind:value={a + b} />\n        <input bind:title={a} />\n        <input bind:value={a} />\n    </form>;\n};\n";
+    let expected = lines([
+        (5, "invalid-binding"),
+        (6, "invalid-binding"),
+        (7, "invalid-binding"),
+        (8, "invalid-binding"),
+    ]);
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(diagnostic_lines(input, tr_opts), expected);
+    // Rendered views are validated the same way
+    let mut tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    tr_opts.mode = OutputMode::Ssr;
+    assert_eq!(diagnostic_lines(input, tr_opts), expected);
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    let Diagnostics(list) = tr_str(input, tr_opts).err().unwrap();
+    assert!(list[0].message.contains("derived"));
+}
+
+#[test]
+fn test_invalid_directive() {
+    let input = "export default (a, key) => (\n    <div>\n        <p class={{ [key]: a }} />\n        <p style={{ ...a }} />\n        <p class:active=<b /> />\n        <p class={{ active: a }} style:color={a} />\n    </div>\n);\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([
+            (3, "invalid-directive"),
+            (4, "invalid-directive"),
+            (5, "invalid-directive")
+        ])
+    );
+}
+
+#[test]
+fn test_invalid_ref() {
+    let input = "export default (a) => (\n    <div ref={a}>\n        <p ref=\"p\" />\n        <p ref />\n        <p ref:my-p />\n    </div>\n);\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([(3, "invalid-ref"), (4, "invalid-ref"), (5, "invalid-ref")])
+    );
+}
+
+#[test]
+fn test_invalid_action() {
+    let input = "import { tooltip } from \"./actions\";\nexport default (a) => (\n    <div use:tooltip={a}>\n        <p use:my-tooltip />\n        <p use:tooltip=<b /> />\n        <i use:a />\n    </div>\n);\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([
+            (4, "invalid-action"),
+            (5, "invalid-action"),
+            (6, "invalid-action")
+        ])
+    );
+}
+
+#[test]
+fn test_invalid_assignment() {
+    let input = "export default (a) => {\n    let b = a + 1;\n    let c = 1;\n    c = c + 1;\n    b = b + 1;\n    a += 1;\n    [c, b] = [b, c];\n    a++;\n    ({ b } = {});\n    [b = 0] = [];\n    for (b of [a]) {}\n    for (const b of [a]) { b.x = 0; }\n    return <p onclick={() => (b = 0)}>{b}</p>;\n};\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([5, 6, 7, 8, 9, 10, 11, 13].map(|line| (line, "invalid-assignment")))
+    );
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    let Diagnostics(list) = tr_str(input, tr_opts).err().unwrap();
+    assert!(list[0].message.contains("\"b\""));
+}
+
+#[test]
+fn test_invalid_state() {
+    let input = "import { state } from \"viewmill-runtime\";\nexport default () => {\n    const open = state(false);\n    return <p>{open}</p>;\n};\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([(3, "invalid-node")])
+    );
+}
+
+#[test]
+fn test_invalid_element() {
+    let input = "export default (a) => <p>{a}</p>;\n/* @view */\nexport const MyView = ({ a }) => <p>{a}</p>;\n";
+    let element_opts = || {
+        let mut tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+        tr_opts.mode = OutputMode::Element;
+        tr_opts.named_views = NamedViews::Marked;
+        tr_opts
+    };
+    assert_eq!(
+        diagnostic_lines(input, element_opts()),
+        lines([(1, "invalid-element"), (3, "invalid-element")])
+    );
+    let Diagnostics(list) = tr_named_str("counter.jsx", input, element_opts())
+        .err()
+        .unwrap();
+    assert!(list[0].message.contains("\"counter\""));
+    let input = "export default (a) => <p>{a}</p>;\n";
+    let output = tr_named_str("src/my-counter.jsx", input, element_opts()).unwrap();
+    assert!(output.src.contains("defineElement(\"my-counter\", View, ["));
+}
+
+#[test]
+fn test_invalid_component() {
+    let input = "export const Title = (text) => <h1>{text}</h1>;\nexport const Card = (props) => props.children;\nexport default (title) => (\n    <div>\n        <Title text={title} />\n        <Card><p /></Card>\n    </div>\n);\n";
+    let mut tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    tr_opts.named_views = NamedViews::Capitalized;
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([(5, "invalid-component")])
+    );
+}
+
+#[test]
+fn test_invalid_pragma() {
+    let input = "export default (a) => {\n    // @viewmill-eq\n    const b = [a];\n    /* @viewmill-eq a.b */\n    const c = [a];\n    return <p>{b}{c}</p>;\n};\n";
+    let tr_opts = Options::try_new(Syntax::Js, None, None).unwrap();
+    assert_eq!(
+        diagnostic_lines(input, tr_opts),
+        lines([(2, "invalid-pragma"), (4, "invalid-pragma")])
+    );
 }
@@ -2,6 +2,7 @@ extern crate wasm_bindgen;
 
 use std::path::Path;
 
+use js_sys::{Array, Reflect};
 use wasm_bindgen::prelude::*;
 
 use transformer::*;
@@ -10,13 +11,34 @@ use transformer::*;
 pub struct JsOptions {
     target: Option<String>,
     verbose: Option<bool>,
+    source_map: Option<String>,
+    named_views: Option<String>,
+    runtime: Option<String>,
+    import_style: Option<String>,
+    mode: Option<String>,
 }
 
 #[wasm_bindgen(js_class = "Options")]
 impl JsOptions {
     #[wasm_bindgen(constructor)]
-    pub fn new(target: Option<String>, verbose: Option<bool>) -> Self {
-        Self { target, verbose }
+    pub fn new(
+        target: Option<String>,
+        verbose: Option<bool>,
+        source_map: Option<String>,
+        named_views: Option<String>,
+        runtime: Option<String>,
+        import_style: Option<String>,
+        mode: Option<String>,
+    ) -> Self {
+        Self {
+            target,
+            verbose,
+            source_map,
+            named_views,
+            runtime,
+            import_style,
+            mode,
+        }
     }
 }
 
@@ -34,20 +56,160 @@ impl JsOutput {
     pub fn ext(&self) -> String {
         self.0.ext.to_string()
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn map(&self) -> Option<String> {
+        self.0.map.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn diagnostics(&self) -> Array {
+        diagnostics_array(&self.0.diagnostics)
+    }
 }
 
-#[wasm_bindgen]
-pub fn transform(file_path: &str, input: &str, options: JsOptions) -> Result<JsOutput, String> {
-    let options = Options::try_new(
+#[wasm_bindgen(js_name = "Diagnostic")]
+pub struct JsDiagnostic(Diagnostic);
+
+#[wasm_bindgen(js_class = "Diagnostic")]
+impl JsDiagnostic {
+    #[wasm_bindgen(getter)]
+    pub fn severity(&self) -> String {
+        self.0.severity.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.0.message.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> Option<String> {
+        self.0.code.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn file(&self) -> Option<String> {
+        self.0.file.clone()
+    }
+
+    /// 1-based
+    #[wasm_bindgen(getter, js_name = "startLine")]
+    pub fn start_line(&self) -> Option<usize> {
+        self.0.range.map(|r| r.start.line)
+    }
+
+    /// 0-based
+    #[wasm_bindgen(getter, js_name = "startColumn")]
+    pub fn start_column(&self) -> Option<usize> {
+        self.0.range.map(|r| r.start.column)
+    }
+
+    /// 1-based
+    #[wasm_bindgen(getter, js_name = "endLine")]
+    pub fn end_line(&self) -> Option<usize> {
+        self.0.range.map(|r| r.end.line)
+    }
+
+    /// 0-based
+    #[wasm_bindgen(getter, js_name = "endColumn")]
+    pub fn end_column(&self) -> Option<usize> {
+        self.0.range.map(|r| r.end.column)
+    }
+
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+fn diagnostics_array(list: &[Diagnostic]) -> Array {
+    list.iter()
+        .map(|d| JsValue::from(JsDiagnostic(d.clone())))
+        .collect()
+}
+
+/// The thrown error has the `diagnostics` property with all the collected errors and warnings.
+fn diagnostics_error(diagnostics: Diagnostics) -> JsValue {
+    let err = js_sys::Error::new(&diagnostics.to_string());
+    _ = Reflect::set(
+        &err,
+        &JsValue::from_str("diagnostics"),
+        &diagnostics_array(&diagnostics.0),
+    );
+    err.into()
+}
+
+fn tr_options(file_path: &str, options: JsOptions) -> Result<Options, JsValue> {
+    let mut tr_options = Options::try_new(
         Syntax::from_path(Path::new(file_path))
             .ok_or_else(|| format!("Unknown file type at \"{file_path}\""))?,
-        options.target.as_ref().map(String::as_str),
+        options.target.as_deref(),
         options.verbose,
     )
     .map_err(|e| e.to_string())?;
-    tr_str(input, options)
+    tr_options.source_map = options.source_map.as_deref().map(str::parse).transpose()?;
+    if let Some(named_views) = options.named_views.as_deref() {
+        tr_options.named_views = named_views.parse()?;
+    }
+    if let Some(runtime) = options.runtime {
+        tr_options.runtime = runtime;
+    }
+    if let Some(import_style) = options.import_style.as_deref() {
+        tr_options.import_style = import_style.parse()?;
+    }
+    if let Some(mode) = options.mode.as_deref() {
+        tr_options.mode = mode.parse()?;
+    }
+    Ok(tr_options)
+}
+
+#[wasm_bindgen]
+pub fn transform(file_path: &str, input: &str, options: JsOptions) -> Result<JsOutput, JsValue> {
+    tr_named_str(file_path, input, tr_options(file_path, options)?)
         .map(JsOutput)
-        .map_err(|e| e.to_string())
+        .map_err(diagnostics_error)
+}
+
+#[wasm_bindgen(js_name = "Check")]
+pub struct JsCheck(Check);
+
+#[wasm_bindgen(js_class = "Check")]
+impl JsCheck {
+    #[wasm_bindgen(getter)]
+    pub fn map(&self) -> Option<String> {
+        self.0.output.map.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn diagnostics(&self) -> Array {
+        diagnostics_array(&self.0.output.diagnostics)
+    }
+
+    /// `undefined` if the existing source is up to date
+    #[wasm_bindgen(getter)]
+    pub fn diff(&self) -> Option<String> {
+        self.0.diff.clone()
+    }
+}
+
+/// Compares the transformed input with its `existing` output without the source map reference.
+#[wasm_bindgen]
+pub fn check(
+    file_path: &str,
+    input: &str,
+    options: JsOptions,
+    existing: &str,
+) -> Result<JsCheck, JsValue> {
+    check_named_str(file_path, input, tr_options(file_path, options)?, existing)
+        .map(JsCheck)
+        .map_err(diagnostics_error)
+}
+
+/// The output file extension for the input path, e.g. "js" for "*.jsx".
+#[wasm_bindgen(js_name = "outputExt")]
+pub fn output_ext(file_path: &str) -> Option<String> {
+    Syntax::from_path(Path::new(file_path)).map(|s| s.ext().to_string())
 }
 
 #[wasm_bindgen(js_name = "displayEsVersions")]
@@ -64,3 +226,8 @@ pub fn display_es_versions() -> String {
         .collect::<Vec<String>>()
         .join(", ")
 }
+
+#[wasm_bindgen]
+pub fn version() -> String {
+    VERSION.to_string()
+}